@@ -0,0 +1,181 @@
+use std::time::Duration;
+use std::{env, process};
+
+use aoc::bench::{self, Baseline, Config, Stage};
+use aoc::input::Inputs;
+use aoc::{solution, Day};
+
+const USAGE: &str = "\
+Usage: cargo bench -- [OPTIONS] [DAY...]
+
+Times parsing and both parts of each given day (or every day) against the
+real inputs, and compares the median times against a saved baseline.
+
+Options:
+    --year <YEAR>           Benchmark days from YEAR. Defaults to every year,
+                            or the latest year if days are given.
+    --baseline <NAME>       Compare against the baseline called NAME.
+                            Defaults to `previous`.
+    --save-baseline <NAME>  Save this run as the baseline called NAME.
+                            Defaults to `previous`, so each run is compared
+                            against the last one.
+    --threshold <PERCENT>   Report a regression when a median is more than
+                            PERCENT slower than the baseline. Defaults to 10.
+    --time <SECONDS>        Spend about this long timing each day. Defaults
+                            to 1.
+    --input-dir <DIR>       Load inputs from DIR. Defaults to $AOC_INPUT_DIR
+                            if it's set, or `input/` in this repository
+                            otherwise.
+    --help                  Print this message.";
+
+const DEFAULT_BASELINE: &str = "previous";
+
+/// Parsed command-line arguments.
+struct Args {
+    baseline: String,
+    save_baseline: String,
+    threshold: f64,
+    config: Config,
+    inputs: Inputs,
+    days: Vec<&'static Day>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut ret = Self {
+            baseline: DEFAULT_BASELINE.to_owned(),
+            save_baseline: DEFAULT_BASELINE.to_owned(),
+            threshold: 10.0,
+            config: Config::default(),
+            inputs: Inputs::from_env(),
+            days: Vec::new(),
+        };
+        let mut year = None;
+        let mut days = Vec::new();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} requires a value", arg))
+            };
+            match arg.as_str() {
+                // Cargo passes this to every benchmark.
+                "--bench" => {}
+                "--baseline" => ret.baseline = value()?,
+                "--save-baseline" => ret.save_baseline = value()?,
+                "--threshold" => {
+                    let threshold = value()?;
+                    ret.threshold = threshold
+                        .parse()
+                        .map_err(|_| format!("Expected a percentage, got {:?}", threshold))?;
+                }
+                "--time" => {
+                    let time = value()?;
+                    ret.config.time = time
+                        .parse()
+                        .ok()
+                        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                        .ok_or_else(|| format!("Expected a number of seconds, got {:?}", time))?;
+                }
+                "--year" => {
+                    let value = value()?;
+                    year = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("Expected a year, got {:?}", value))?,
+                    );
+                }
+                "--input-dir" => ret.inputs = Inputs::new(value()?),
+                _ if arg.starts_with("--") => return Err(format!("Unknown option {:?}", arg)),
+                _ => days.push(
+                    arg.parse()
+                        .map_err(|_| format!("Expected a day number, got {:?}", arg))?,
+                ),
+            }
+        }
+        ret.days = solution::select(year, &days)?;
+        Ok(ret)
+    }
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let dir = Baseline::dir();
+    let baseline_path = Baseline::path(&dir, &args.baseline);
+    let baseline = Baseline::load(&baseline_path).map_err(|err| err.to_string())?;
+    // Days that aren't benchmarked this run keep their old times.
+    let save_path = Baseline::path(&dir, &args.save_baseline);
+    let mut saved = Baseline::load(&save_path)
+        .map_err(|err| err.to_string())?
+        .unwrap_or_default();
+
+    println!(
+        "{:>4}  {:>3}  {:<6}  {:>9}  {:>9}  {:>7}  {:>8}",
+        "year", "day", "stage", "median", "min", "samples", "change"
+    );
+    let mut regressions = 0;
+    for day in &args.days {
+        let input = args
+            .inputs
+            .load(day.year, day.name)
+            .map_err(|err| err.to_string())?;
+        let samples = bench::measure(day, &input, &args.config)
+            .map_err(|err| format!("Bad input for {}: {}", day, err))?;
+        for stage in Stage::ALL {
+            let samples = &samples[&stage];
+            let change = baseline
+                .as_ref()
+                .and_then(|baseline| baseline.get(day.year, day.day, stage))
+                .and_then(|old| bench::percent_change(old, samples.median()));
+            let regressed = change.is_some_and(|change| change > args.threshold);
+            if regressed {
+                regressions += 1;
+            }
+            let row = format!(
+                "{:>4}  {:>3}  {:<6}  {:>9}  {:>9}  {:>7}  {:>8}{}",
+                day.year,
+                day.day,
+                stage,
+                bench::format_duration(samples.median()),
+                bench::format_duration(samples.min()),
+                samples.count(),
+                change.map_or_else(String::new, |change| format!("{:+.1}%", change)),
+                if regressed { "  regressed" } else { "" },
+            );
+            println!("{}", row.trim_end());
+            saved.insert(day.year, day.day, stage, samples.median());
+        }
+    }
+
+    match baseline {
+        Some(_) if regressions > 0 => println!(
+            "\n{} regressed by more than {}% since baseline {:?}",
+            if regressions == 1 {
+                "1 stage".to_owned()
+            } else {
+                format!("{} stages", regressions)
+            },
+            args.threshold,
+            args.baseline
+        ),
+        Some(_) => println!("\nNo regressions since baseline {:?}", args.baseline),
+        None => println!("\nNo baseline {:?} to compare against", args.baseline),
+    }
+    saved.save(&save_path).map_err(|err| err.to_string())?;
+    println!(
+        "Saved baseline {:?} to {}",
+        args.save_baseline,
+        save_path.display()
+    );
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return;
+    }
+    if let Err(err) = Args::parse(args.into_iter()).and_then(|args| run(&args)) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
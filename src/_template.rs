@@ -1,25 +1,49 @@
 use crate::*;
 
-type Input = ();
+type Input = String;
 
-pub fn part_1(input: Input) -> usize {
+pub fn part_1(_input: Input) -> usize {
     0
 }
 
-pub fn part_2(input: Input) -> usize {
+pub fn part_2(_input: Input) -> usize {
     0
 }
 
+pub struct Template;
+
+impl Solution for Template {
+    const YEAR: u16 = 0;
+    const DAY: u8 = 0;
+    const NAME: &'static str = "_template";
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.to_owned())
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part_2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     fn sample() -> Input {
-        todo!()
+        Template::parse("").unwrap()
     }
 
     fn input() -> Input {
-        todo!()
+        Template::parse(&input!(0, "_template")).unwrap()
     }
 
     #[test]
@@ -28,6 +52,7 @@ mod tests {
     }
 
     #[test]
+    #[ignore = "needs the real input and its answer"]
     fn test_part_1() {
         assert_eq!(part_1(input()), 0);
     }
@@ -38,6 +63,7 @@ mod tests {
     }
 
     #[test]
+    #[ignore = "needs the real input and its answer"]
     fn test_part_2() {
         assert_eq!(part_2(input()), 0);
     }
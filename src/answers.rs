@@ -0,0 +1,228 @@
+//! A manifest of known answers, so the runner can check solutions against any
+//! set of inputs without editing the tests. Run with `aoc --check`.
+
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use crate::input::Inputs;
+use crate::*;
+
+/// The manifest's file name in the input directory.
+pub const FILE_NAME: &str = "answers.tsv";
+
+/// The expected answer to one part of one day, for one input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    /// The input's file name in the year's input directory.
+    pub input: String,
+    pub expected: String,
+}
+
+/// The result of checking an answer against the manifest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// There's no answer in the manifest for this year, day, part and input.
+    Unknown,
+}
+
+/// The answers in a manifest, in the order they're listed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers(Vec<Answer>);
+
+impl Answers {
+    /// The manifest that goes with a directory of inputs.
+    pub fn path(inputs: &Inputs) -> PathBuf {
+        inputs.dir().join(FILE_NAME)
+    }
+
+    /// Parse a manifest of tab-separated `year`, `day`, `part`, `input` and
+    /// `answer` lines. Blank lines and lines starting with `#` are skipped. Answers
+    /// can't contain tabs or newlines, so those (and backslashes) are escaped
+    /// as `\t`, `\n` and `\\`.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut ret = Vec::new();
+        for (i, line) in contents.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |what: &str| format!("Bad {} on line {}: {:?}", what, i + 1, line);
+            let (year, day, part, input, expected) = line
+                .split('\t')
+                .collect_tuple()
+                .ok_or_else(|| invalid("line"))?;
+            ret.push(Answer {
+                year: year.parse().map_err(|_| invalid("year"))?,
+                day: day.parse().map_err(|_| invalid("day"))?,
+                part: part.parse().map_err(|_| invalid("part"))?,
+                input: input.to_owned(),
+                expected: unescape(expected).ok_or_else(|| invalid("answer"))?,
+            });
+        }
+        Ok(Self(ret))
+    }
+
+    /// Load a manifest, or `None` if there isn't one.
+    pub fn load(path: &Path) -> io::Result<Option<Self>> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        Self::parse(&contents).map(Some).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), err),
+            )
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Answer> {
+        self.0.iter()
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: Part, input: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|answer| {
+                answer.year == year
+                    && answer.day == day
+                    && answer.part == part
+                    && answer.input == input
+            })
+            .map(|answer| answer.expected.as_str())
+    }
+
+    /// Check `actual` against the expected answer. Trailing whitespace is
+    /// ignored, since multi-line answers end with a newline.
+    pub fn check(&self, year: u16, day: u8, part: Part, input: &str, actual: &str) -> Status {
+        match self.get(year, day, part, input) {
+            None => Status::Unknown,
+            Some(expected) if expected.trim_end() == actual.trim_end() => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.to_owned(),
+            },
+        }
+    }
+}
+
+/// Escape an answer to fit in one field of the manifest.
+pub fn escape(answer: &str) -> String {
+    answer
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(field: &str) -> Option<String> {
+    let mut ret = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            ret.push(match chars.next()? {
+                '\\' => '\\',
+                't' => '\t',
+                'n' => '\n',
+                _ => return None,
+            });
+        } else {
+            ret.push(c);
+        }
+    }
+    Some(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Answers {
+        Answers::parse(
+            "# year\tday\tpart\tinput\tanswer\n\
+             \n\
+             2021\t1\t1\tday_1_sonar_sweep.txt\t1215\n\
+             2021\t13\t2\tday_13_transparent_origami.txt\t#.\\n.#\\n\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            sample().iter().cloned().collect::<Vec<_>>(),
+            [
+                Answer {
+                    year: 2021,
+                    day: 1,
+                    part: Part::One,
+                    input: "day_1_sonar_sweep.txt".to_owned(),
+                    expected: "1215".to_owned(),
+                },
+                Answer {
+                    year: 2021,
+                    day: 13,
+                    part: Part::Two,
+                    input: "day_13_transparent_origami.txt".to_owned(),
+                    expected: "#.\n.#\n".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            Answers::parse("2021\t1\t3\tday_1.txt\t1").unwrap_err(),
+            "Bad part on line 1: \"2021\\t1\\t3\\tday_1.txt\\t1\""
+        );
+        assert!(Answers::parse("2021\t1\t1\tday_1.txt").is_err());
+        assert!(Answers::parse("2021\t1\t1\tday_1.txt\ta\\b").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = sample();
+        let input = "day_1_sonar_sweep.txt";
+        assert_eq!(
+            answers.check(2021, 1, Part::One, input, "1215"),
+            Status::Pass
+        );
+        assert_eq!(
+            answers.check(2021, 1, Part::One, input, "1216"),
+            Status::Fail {
+                expected: "1215".to_owned()
+            }
+        );
+        assert_eq!(
+            answers.check(2021, 1, Part::Two, input, "1150"),
+            Status::Unknown
+        );
+        assert_eq!(
+            answers.check(2021, 1, Part::One, "other.txt", "1215"),
+            Status::Unknown
+        );
+        assert_eq!(
+            answers.check(2020, 1, Part::One, input, "1215"),
+            Status::Unknown
+        );
+        assert_eq!(
+            answers.check(
+                2021,
+                13,
+                Part::Two,
+                "day_13_transparent_origami.txt",
+                "#.\n.#"
+            ),
+            Status::Pass
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        let answer = "a\\b\tc\nd";
+        assert_eq!(escape(answer), "a\\\\b\\tc\\nd");
+        assert_eq!(unescape(&escape(answer)).as_deref(), Some(answer));
+    }
+}
@@ -0,0 +1,292 @@
+//! Timing each day's parser and solutions against the real inputs, and
+//! saving the results so later runs can be compared against them. Run with
+//! `cargo bench`; see `benches/solutions.rs`.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use std::{fs, io};
+
+use crate::*;
+
+/// One thing being timed: parsing the input, or solving a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [
+        Stage::Parse,
+        Stage::Solve(Part::One),
+        Stage::Solve(Part::Two),
+    ];
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Solve(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            _ => s
+                .strip_prefix("part ")
+                .ok_or_else(|| format!("Stage must be \"parse\" or \"part N\", got {:?}", s))?
+                .parse()
+                .map(Stage::Solve),
+        }
+    }
+}
+
+/// The durations of repeated runs of a stage, sorted from fastest to slowest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Samples(Vec<Duration>);
+
+impl Samples {
+    /// # Panics
+    /// If `samples` is empty.
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "Can't summarize zero samples");
+        samples.sort_unstable();
+        Self(samples)
+    }
+
+    /// The median is what's compared between runs, since it's less noisy
+    /// than the mean.
+    pub fn median(&self) -> Duration {
+        self.0[self.0.len() / 2]
+    }
+
+    pub fn min(&self) -> Duration {
+        self.0[0]
+    }
+
+    /// How many samples were taken.
+    pub fn count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// How long to spend timing each day.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Keep taking samples until this much time has passed...
+    pub time: Duration,
+    /// ...or there are this many samples, but take at least `min_samples`.
+    pub max_samples: usize,
+    pub min_samples: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            time: Duration::from_secs(1),
+            max_samples: 1000,
+            min_samples: 5,
+        }
+    }
+}
+
+/// Time each stage of a day against `input`.
+///
+/// Every sample parses the input again, since solving a part consumes the
+/// parsed input. That means there are twice as many parse samples as there
+/// are samples for each part.
+pub fn measure(day: &Day, input: &str, config: &Config) -> Result<BTreeMap<Stage, Samples>, Error> {
+    // Warm up, and fail early on a bad input.
+    for part in Part::ALL {
+        day.time(input, part)?;
+    }
+
+    let mut samples: BTreeMap<Stage, Vec<Duration>> = BTreeMap::new();
+    let start = Instant::now();
+    for i in 0..config.max_samples {
+        if i >= config.min_samples && start.elapsed() >= config.time {
+            break;
+        }
+        for part in Part::ALL {
+            let timing = day.time(input, part)?;
+            samples.entry(Stage::Parse).or_default().push(timing.parse);
+            samples
+                .entry(Stage::Solve(part))
+                .or_default()
+                .push(timing.solve);
+        }
+    }
+
+    Ok(samples
+        .into_iter()
+        .map(|(stage, samples)| (stage, Samples::new(samples)))
+        .collect())
+}
+
+/// Median timings from an earlier run, keyed by year, day and stage.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Baseline(BTreeMap<(u16, u8, Stage), Duration>);
+
+impl Baseline {
+    /// Where baselines are saved.
+    pub fn dir() -> PathBuf {
+        PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/target/aoc-bench"))
+    }
+
+    /// The path of the baseline called `name` in `dir`.
+    pub fn path(dir: &Path, name: &str) -> PathBuf {
+        dir.join(name).with_extension("tsv")
+    }
+
+    pub fn get(&self, year: u16, day: u8, stage: Stage) -> Option<Duration> {
+        self.0.get(&(year, day, stage)).copied()
+    }
+
+    pub fn insert(&mut self, year: u16, day: u8, stage: Stage, median: Duration) {
+        self.0.insert((year, day, stage), median);
+    }
+
+    /// Load a baseline, or `None` if it hasn't been saved yet.
+    pub fn load(path: &Path) -> io::Result<Option<Self>> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let invalid = |line: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Bad line in baseline {}: {:?}", path.display(), line),
+            )
+        };
+        let mut ret = Self::default();
+        for line in contents.lines() {
+            let (year, day, stage, nanos) = line
+                .split('\t')
+                .collect_tuple()
+                .ok_or_else(|| invalid(line))?;
+            ret.insert(
+                year.parse().map_err(|_| invalid(line))?,
+                day.parse().map_err(|_| invalid(line))?,
+                stage.parse().map_err(|_| invalid(line))?,
+                Duration::from_nanos(nanos.parse().map_err(|_| invalid(line))?),
+            );
+        }
+        Ok(Some(ret))
+    }
+
+    /// Save the baseline as tab-separated `year`, `day`, `stage`, and
+    /// nanoseconds.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents: String = self
+            .0
+            .iter()
+            .map(|((year, day, stage), median)| {
+                format!("{}\t{}\t{}\t{}\n", year, day, stage, median.as_nanos())
+            })
+            .collect();
+        fs::write(path, contents)
+    }
+}
+
+/// The change from `old` to `new` as a percentage of `old`; positive if
+/// `new` is slower, or `None` if `old` is zero, which has no percentage.
+pub fn percent_change(old: Duration, new: Duration) -> Option<f64> {
+    (!old.is_zero()).then(|| (new.as_secs_f64() - old.as_secs_f64()) / old.as_secs_f64() * 100.0)
+}
+
+/// Format a duration in the largest unit that keeps it above 1, like
+/// `"12.3ms"`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    if nanos < 1e3 {
+        format!("{}ns", nanos)
+    } else if nanos < 1e6 {
+        format!("{:.1}µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.1}ms", nanos / 1e6)
+    } else {
+        format!("{:.2}s", nanos / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_samples() {
+        let samples = Samples::new(
+            [5, 1, 4, 2, 3]
+                .into_iter()
+                .map(Duration::from_millis)
+                .collect(),
+        );
+        assert_eq!(samples.median(), Duration::from_millis(3));
+        assert_eq!(samples.min(), Duration::from_millis(1));
+        assert_eq!(samples.count(), 5);
+    }
+
+    #[test]
+    fn test_measure() {
+        let config = Config {
+            time: Duration::ZERO,
+            max_samples: 10,
+            min_samples: 3,
+        };
+        let samples = measure(&YEARS[0].days[0], "1\n2\n3\n4\n", &config).unwrap();
+        assert_eq!(samples.keys().copied().collect::<Vec<_>>(), Stage::ALL);
+        assert_eq!(samples[&Stage::Parse].count(), 6);
+        assert_eq!(samples[&Stage::Solve(Part::One)].count(), 3);
+        assert!(measure(&YEARS[0].days[0], "x", &config).is_err());
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let dir = std::env::temp_dir().join(format!("aoc-bench-test-{}", std::process::id()));
+        let path = Baseline::path(&dir, "test");
+        assert_eq!(Baseline::load(&path).unwrap(), None);
+
+        let mut baseline = Baseline::default();
+        baseline.insert(2021, 1, Stage::Parse, Duration::from_nanos(1234));
+        baseline.insert(2021, 19, Stage::Solve(Part::Two), Duration::from_millis(50));
+        baseline.save(&path).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "2021\t1\tparse\t1234\n2021\t19\tpart 2\t50000000\n"
+        );
+        assert_eq!(Baseline::load(&path).unwrap(), Some(baseline));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.3µs");
+        assert_eq!(format_duration(Duration::from_micros(4_560)), "4.6ms");
+        assert_eq!(format_duration(Duration::from_millis(1_500)), "1.50s");
+    }
+
+    #[test]
+    fn test_percent_change() {
+        let change = percent_change(Duration::from_millis(100), Duration::from_millis(110));
+        assert!((change.unwrap() - 10.0).abs() < 1e-9);
+        assert_eq!(
+            percent_change(Duration::ZERO, Duration::from_millis(110)),
+            None
+        );
+    }
+}
@@ -0,0 +1,355 @@
+use std::fmt::Display;
+use std::num::{IntErrorKind, ParseIntError};
+use std::str::FromStr;
+
+use crate::*;
+
+/// A place in the input. Lines and columns count from 1, and columns count
+/// characters rather than bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// The position of `fragment` in `source`.
+    ///
+    /// `fragment` should be a slice of `source`; if it's not, this is the
+    /// start of `source`.
+    pub fn of(source: &str, fragment: &str) -> Self {
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(source.as_ptr() as usize)
+            .filter(|offset| *offset <= source.len())
+            .unwrap_or(0);
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    /// Convert a position in a fragment of some larger input into a position
+    /// in the larger input, given the position of the fragment's start.
+    fn offset_by(self, start: Position) -> Self {
+        if self.line == 1 {
+            Self {
+                line: start.line,
+                column: start.column + self.column - 1,
+            }
+        } else {
+            Self {
+                line: start.line + self.line - 1,
+                column: self.column,
+            }
+        }
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self { line: 1, column: 1 }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// What went wrong.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A character that doesn't belong here, like a letter in a number.
+    UnexpectedChar { found: char, expected: &'static str },
+    /// A word that doesn't belong here, like an unknown command.
+    UnexpectedToken {
+        found: String,
+        expected: &'static str,
+    },
+    /// The input ended before something was found.
+    UnexpectedEnd { expected: &'static str },
+    /// A delimiter or keyword (like `" -> "`) is missing.
+    Missing(&'static str),
+    /// The wrong number of fields, like a bingo board without 25 numbers.
+    WrongFieldCount {
+        what: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// An integer is too large or small for its type.
+    IntegerOverflow,
+    /// There's input left over after parsing finished.
+    TrailingInput,
+    /// The input parsed but has no answer, like a bingo game nobody wins.
+    NoSolution(&'static str),
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorKind::UnexpectedChar { found, expected } => {
+                write!(f, "unexpected character {:?}, expected {}", found, expected)
+            }
+            ErrorKind::UnexpectedToken { found, expected } => {
+                write!(f, "unexpected {:?}, expected {}", found, expected)
+            }
+            ErrorKind::UnexpectedEnd { expected } => {
+                write!(f, "unexpected end of input, expected {}", expected)
+            }
+            ErrorKind::Missing(expected) => write!(f, "expected {:?}", expected),
+            ErrorKind::WrongFieldCount {
+                what,
+                expected,
+                found,
+            } => write!(f, "expected {} {}, found {}", expected, what, found),
+            ErrorKind::IntegerOverflow => write!(f, "integer out of range"),
+            ErrorKind::TrailingInput => write!(f, "unexpected input after the end"),
+            ErrorKind::NoSolution(reason) => write!(f, "no solution: {}", reason),
+        }
+    }
+}
+
+/// An error in a puzzle input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    position: Option<Position>,
+}
+
+impl Error {
+    /// An error at the start of the input.
+    pub fn new(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            position: Some(Position::default()),
+        }
+    }
+
+    /// An error at the start of `fragment`, which is a slice of `source`.
+    pub fn at(source: &str, fragment: &str, kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            position: Some(Position::of(source, fragment)),
+        }
+    }
+
+    /// An error found while solving a puzzle rather than parsing it, which
+    /// doesn't point at any one place in the input.
+    pub fn no_solution(reason: &'static str) -> Self {
+        Self {
+            kind: ErrorKind::NoSolution(reason),
+            position: None,
+        }
+    }
+
+    /// An integer overflow found while solving a puzzle rather than parsing
+    /// it, where the answer or a step towards it doesn't fit its type.
+    pub fn overflow() -> Self {
+        Self {
+            kind: ErrorKind::IntegerOverflow,
+            position: None,
+        }
+    }
+
+    /// An error at a position computed by the caller, for inputs that aren't
+    /// parsed directly from a `&str`.
+    pub fn at_position(position: Position, kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            position: Some(position),
+        }
+    }
+
+    /// An error at the end of `fragment`, which is a slice of `source`.
+    pub fn after(source: &str, fragment: &str, kind: ErrorKind) -> Self {
+        Self::at(source, &fragment[fragment.len()..], kind)
+    }
+
+    /// Convert an error from parsing `fragment` into an error in `source`,
+    /// which contains `fragment`.
+    pub fn within(self, source: &str, fragment: &str) -> Self {
+        Self {
+            position: self
+                .position
+                .map(|position| position.offset_by(Position::of(source, fragment))),
+            ..self
+        }
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    pub fn position(&self) -> Option<Position> {
+        self.position
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "{}: {}", position, self.kind),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Parse `fragment`, a slice of `source`, reporting errors relative to
+/// `source`.
+pub(crate) fn parse_at<T>(source: &str, fragment: &str) -> Result<T, Error>
+where
+    T: FromStr<Err = Error>,
+{
+    fragment
+        .parse()
+        .map_err(|err: Error| err.within(source, fragment))
+}
+
+/// Parse an integer from `fragment`, a slice of `source`.
+pub(crate) fn parse_int<T>(source: &str, fragment: &str) -> Result<T, Error>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    fragment
+        .parse()
+        .map_err(|err: ParseIntError| match err.kind() {
+            IntErrorKind::Empty => Error::at(
+                source,
+                fragment,
+                ErrorKind::UnexpectedEnd {
+                    expected: "an integer",
+                },
+            ),
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                Error::at(source, fragment, ErrorKind::IntegerOverflow)
+            }
+            _ => {
+                // Find the offending character; a leading sign is fine.
+                let (index, found) = fragment
+                    .char_indices()
+                    .find(|(i, c)| !c.is_ascii_digit() && !(*i == 0 && (*c == '-' || *c == '+')))
+                    .unwrap_or((0, fragment.chars().next().unwrap_or(' ')));
+                Error::at(
+                    source,
+                    &fragment[index..],
+                    ErrorKind::UnexpectedChar {
+                        found,
+                        expected: "a digit",
+                    },
+                )
+            }
+        })
+}
+
+/// Split `fragment`, a slice of `source`, at the first `delimiter`.
+pub(crate) fn expect_split<'a>(
+    source: &str,
+    fragment: &'a str,
+    delimiter: &'static str,
+) -> Result<(&'a str, &'a str), Error> {
+    fragment
+        .split_once(delimiter)
+        .ok_or_else(|| Error::after(source, fragment, ErrorKind::Missing(delimiter)))
+}
+
+/// Strip `prefix` from `fragment`, a slice of `source`.
+pub(crate) fn expect_prefix<'a>(
+    source: &str,
+    fragment: &'a str,
+    prefix: &'static str,
+) -> Result<&'a str, Error> {
+    fragment
+        .strip_prefix(prefix)
+        .ok_or_else(|| Error::at(source, fragment, ErrorKind::Missing(prefix)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_of() {
+        let source = "abc\ndéf\nghi";
+        assert_eq!(
+            Position::of(source, source),
+            Position { line: 1, column: 1 }
+        );
+        assert_eq!(
+            Position::of(source, &source[1..]),
+            Position { line: 1, column: 2 }
+        );
+        assert_eq!(
+            Position::of(source, &source[4..]),
+            Position { line: 2, column: 1 }
+        );
+        assert_eq!(
+            Position::of(source, source.lines().nth(1).unwrap().split_at(3).1),
+            Position { line: 2, column: 3 }
+        );
+        assert_eq!(
+            Position::of(source, &source[source.len()..]),
+            Position { line: 3, column: 4 }
+        );
+        // A string that isn't a slice of `source`. (Not a literal, which might be
+        // laid out right after `source`.)
+        let other = String::from("xyz");
+        assert_eq!(Position::of(source, &other), Position::default());
+    }
+
+    #[test]
+    fn test_within() {
+        let source = "1,2\n3,x";
+        let line = source.lines().nth(1).unwrap();
+        let err = parse_int::<u8>(line, &line[2..]).unwrap_err();
+        assert_eq!(err.position(), Some(Position { line: 1, column: 3 }));
+        let err = err.within(source, line);
+        assert_eq!(err.position(), Some(Position { line: 2, column: 3 }));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: unexpected character 'x', expected a digit"
+        );
+    }
+
+    #[test]
+    fn test_parse_int() {
+        let source = "12 -3 4a 300";
+        let fields: Vec<&str> = source.split(' ').collect();
+        assert_eq!(parse_int::<u8>(source, fields[0]), Ok(12));
+        assert_eq!(parse_int::<i8>(source, fields[1]), Ok(-3));
+        assert_eq!(
+            parse_int::<u8>(source, fields[2]).unwrap_err(),
+            Error {
+                kind: ErrorKind::UnexpectedChar {
+                    found: 'a',
+                    expected: "a digit"
+                },
+                position: Some(Position { line: 1, column: 8 }),
+            }
+        );
+        assert_eq!(
+            parse_int::<u8>(source, fields[3]).unwrap_err().kind(),
+            &ErrorKind::IntegerOverflow
+        );
+        assert_eq!(
+            parse_int::<u8>(source, &source[..0]).unwrap_err().kind(),
+            &ErrorKind::UnexpectedEnd {
+                expected: "an integer"
+            }
+        );
+    }
+
+    #[test]
+    fn test_expect_split() {
+        let source = "0,9 -> 5,9\n8,0 - 0,8";
+        let line = source.lines().nth(1).unwrap();
+        assert_eq!(
+            expect_split(source, line, " -> ").unwrap_err().to_string(),
+            "line 2, column 10: expected \" -> \""
+        );
+    }
+}
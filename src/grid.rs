@@ -0,0 +1,425 @@
+//! A rectangular grid of cells, like the height maps and images that many
+//! puzzles are drawn on.
+
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::*;
+
+/// A point in a [`Grid`], as `(x, y)`: `x` is the column, counting right
+/// from the left edge, and `y` is the row, counting down from the top.
+pub type Point = (usize, usize);
+
+/// The offsets to the 4 orthogonally adjacent points.
+pub const NEIGHBORS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// The offsets to the 8 orthogonally and diagonally adjacent points.
+pub const NEIGHBORS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A non-empty rectangle of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to `value`.
+    ///
+    /// # Panics
+    /// If `width` or `height` is 0.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(width, height, |_| value.clone())
+    }
+
+    /// A grid with each cell set to `f` of its point, in row order.
+    ///
+    /// # Panics
+    /// If `width` or `height` is 0.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        assert!(width > 0 && height > 0, "Grids can't be empty");
+        Self {
+            width,
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(&mut f)
+                .collect(),
+        }
+    }
+
+    /// Parse `s` with one character per cell and one line per row.
+    /// `expected` describes the grid, for when `s` is empty.
+    pub fn parse_with(
+        s: &str,
+        expected: &'static str,
+        mut parse_cell: impl FnMut(char) -> Result<T, ErrorKind>,
+    ) -> Result<Self, Error> {
+        let mut cells = Vec::with_capacity(s.len());
+        let mut width = None;
+        for line in s.lines() {
+            let row_start = cells.len();
+            for (i, c) in line.char_indices() {
+                cells.push(parse_cell(c).map_err(|kind| Error::at(s, &line[i..], kind))?);
+            }
+            let found = cells.len() - row_start;
+            // Every row should be as wide as the first, which can't be empty.
+            let expected = *width.get_or_insert(found.max(1));
+            if found != expected {
+                return Err(Error::at(
+                    s,
+                    line,
+                    ErrorKind::WrongFieldCount {
+                        what: "cells in this row",
+                        expected,
+                        found,
+                    },
+                ));
+            }
+        }
+        match width {
+            Some(width) => Ok(Self { width, cells }),
+            None => Err(Error::after(s, s, ErrorKind::UnexpectedEnd { expected })),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len() / self.width
+    }
+
+    /// The number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Always false, since grids can't be empty.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (x, y): Point) -> bool {
+        x < self.width && y < self.height()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        if self.contains(point) {
+            Some(&self.cells[self.index_of(point)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            let i = self.index_of(point);
+            Some(&mut self.cells[i])
+        } else {
+            None
+        }
+    }
+
+    /// The point `(dx, dy)` away from `point`, if it's in the grid.
+    pub fn offset(&self, point: Point, delta: (isize, isize)) -> Option<Point> {
+        offset((self.width, self.height()), point, delta)
+    }
+
+    /// The point `(dx, dy)` away from `point`, wrapping around the edges as if
+    /// the grid were a torus.
+    pub fn wrapping_offset(&self, point: Point, delta: (isize, isize)) -> Point {
+        wrapping_offset((self.width, self.height()), point, delta)
+    }
+
+    /// The points above, left, right and below `point` that are in the grid.
+    /// These don't borrow the grid, so it can be changed while they're
+    /// visited.
+    pub fn neighbors_4(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.neighbors(point, &NEIGHBORS_4)
+    }
+
+    /// The points orthogonally or diagonally adjacent to `point` that are in
+    /// the grid.
+    pub fn neighbors_8(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.neighbors(point, &NEIGHBORS_8)
+    }
+
+    /// The points at each of `offsets` from `point` that are in the grid.
+    pub fn neighbors<'a>(
+        &self,
+        point: Point,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Point> + 'a {
+        let size = (self.width, self.height());
+        offsets
+            .iter()
+            .filter_map(move |&delta| offset(size, point, delta))
+    }
+
+    /// The points at each of `offsets` from `point`, wrapping around the edges
+    /// like [`wrapping_offset`](Self::wrapping_offset).
+    pub fn wrapping_neighbors<'a>(
+        &self,
+        point: Point,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Point> + 'a {
+        let size = (self.width, self.height());
+        offsets
+            .iter()
+            .map(move |&delta| wrapping_offset(size, point, delta))
+    }
+
+    /// Every point, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height()).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell, row by row.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every point and its cell, row by row.
+    pub fn enumerate(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// # Panics
+    /// If `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..][..self.width]
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.cells.chunks(self.width)
+    }
+
+    /// # Panics
+    /// If `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is out of bounds", x);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// A grid of the same size with `f` applied to each cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn index_of(&self, (x, y): Point) -> usize {
+        y * self.width + x
+    }
+}
+
+impl Grid<u8> {
+    /// Parse a grid of single decimal digits, like `"123\n456\n"`.
+    pub fn parse_digits(s: &str) -> Result<Self, Error> {
+        Self::parse_with(s, "a grid of digits", |c| match c.to_digit(10) {
+            Some(digit) => Ok(digit as u8),
+            None => Err(ErrorKind::UnexpectedChar {
+                found: c,
+                expected: "a digit",
+            }),
+        })
+    }
+}
+
+impl Grid<bool> {
+    /// Parse a grid of pixels, where `#` is lit and `.` is dark.
+    pub fn parse_pixels(s: &str) -> Result<Self, Error> {
+        Self::parse_with(s, "a grid of pixels", |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(ErrorKind::UnexpectedChar {
+                found: c,
+                expected: "'#' or '.'",
+            }),
+        })
+    }
+
+    /// Draw lit pixels as `#` and dark ones as `.`.
+    pub fn pixels(&self) -> Grid<char> {
+        self.map(|&lit| if lit { '#' } else { '.' })
+    }
+}
+
+fn offset(
+    (width, height): (usize, usize),
+    (x, y): Point,
+    (dx, dy): (isize, isize),
+) -> Option<Point> {
+    let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+    Some((x, y)).filter(|_| x < width && y < height)
+}
+
+fn wrapping_offset(
+    (width, height): (usize, usize),
+    (x, y): Point,
+    (dx, dy): (isize, isize),
+) -> Point {
+    let wrap =
+        |n: usize, dn: isize, len: usize| (n as isize + dn).rem_euclid(len as isize) as usize;
+    (wrap(x, dx, width), wrap(y, dy, height))
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    /// # Panics
+    /// If `point` is out of bounds.
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} is out of bounds", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is out of bounds", point))
+    }
+}
+
+/// One row per line, with no separators between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<u8> {
+        Grid::parse_digits("123\n456\n").unwrap()
+    }
+
+    #[test]
+    fn test_parse_digits() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height(), grid.len()), (3, 2, 6));
+        assert_eq!(grid[(0, 0)], 1);
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.to_string(), "123\n456\n");
+
+        assert_eq!(
+            Grid::parse_digits("12\n3x\n").unwrap_err().to_string(),
+            "line 2, column 2: unexpected character 'x', expected a digit"
+        );
+        assert_eq!(
+            Grid::parse_digits("1A\n").unwrap_err().to_string(),
+            "line 1, column 2: unexpected character 'A', expected a digit"
+        );
+        assert_eq!(
+            Grid::parse_digits("12\n345\n").unwrap_err().to_string(),
+            "line 2, column 1: expected 2 cells in this row, found 3"
+        );
+        assert!(matches!(
+            Grid::parse_digits("").unwrap_err().kind(),
+            ErrorKind::UnexpectedEnd { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_pixels() {
+        let grid = Grid::parse_pixels("#.\n.#").unwrap();
+        assert_eq!(grid.iter().filter(|&&lit| lit).count(), 2);
+        assert_eq!(grid.pixels().to_string(), "#.\n.#\n");
+        assert!(Grid::parse_pixels("#.\n.x").is_err());
+    }
+
+    #[test]
+    fn test_get() {
+        let mut grid = sample();
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        *grid.get_mut((1, 1)).unwrap() = 0;
+        grid[(2, 0)] += 1;
+        assert_eq!(grid.to_string(), "124\n406\n");
+        assert_eq!(grid.offset((0, 1), (2, -1)), Some((2, 0)));
+        assert_eq!(grid.offset((0, 1), (-1, 0)), None);
+        assert_eq!(grid.wrapping_offset((0, 1), (-1, 0)), (2, 1));
+        assert_eq!(grid.wrapping_offset((2, 1), (4, 3)), (0, 0));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 3, ());
+        assert_eq!(
+            grid.neighbors_4((1, 1)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1), (2, 1), (1, 2)]
+        );
+        assert_eq!(
+            grid.neighbors_4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors_8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.wrapping_neighbors((0, 0), &NEIGHBORS_4)
+                .collect::<Vec<_>>(),
+            [(0, 2), (2, 0), (1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbors_8((2, 2)).collect::<Vec<_>>(),
+            [(1, 1), (2, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = sample();
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.sum::<u8>())
+                .collect::<Vec<_>>(),
+            [5, 7, 9]
+        );
+        assert_eq!(
+            grid.points().collect::<Vec<_>>(),
+            [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        );
+        assert_eq!(grid.enumerate().nth(4), Some(((1, 1), &5)));
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let grid = Grid::from_fn(2, 3, |(x, y)| x * 10 + y);
+        assert_eq!(grid.row(2), [2, 12]);
+        assert_eq!(grid.map(|cell| cell % 10).to_string(), "00\n11\n22\n");
+    }
+}
@@ -0,0 +1,146 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use crate::*;
+
+/// Environment variable naming a directory to load inputs from, instead of
+/// the `input/` directory in this repository.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// A directory of puzzle inputs, in a subdirectory for each year and named
+/// `<name>.txt` after their day's module (see [`Solution::NAME`]), and
+/// optionally their answers (see [`answers`]).
+///
+/// ```text
+/// input/
+///     answers.tsv
+///     2021/
+///         day_1_sonar_sweep.txt
+///         ...
+/// ```
+#[derive(Clone, Debug)]
+pub struct Inputs {
+    dir: PathBuf,
+}
+
+impl Inputs {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The directory named by [`INPUT_DIR_VAR`], if it's set, or the `input/`
+    /// directory in this repository otherwise.
+    pub fn from_env() -> Self {
+        match env::var_os(INPUT_DIR_VAR) {
+            Some(dir) => Self::new(dir),
+            None => Self::new(concat!(env!("CARGO_MANIFEST_DIR"), "/input")),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The directory of inputs for `year`.
+    pub fn year_dir(&self, year: u16) -> PathBuf {
+        self.dir.join(year.to_string())
+    }
+
+    /// The path the input named `name` for `year` is loaded from.
+    pub fn path(&self, year: u16, name: &str) -> PathBuf {
+        self.year_dir(year).join(format!("{}.txt", name))
+    }
+
+    /// Load the input named `name` for `year`.
+    pub fn load(&self, year: u16, name: &str) -> Result<String, InputError> {
+        load_path(self.path(year, name)).map_err(|err| match err {
+            InputError::NotFound(path) => InputError::Missing(name.to_owned(), path),
+            err => err,
+        })
+    }
+}
+
+/// Load an input from an explicit path.
+pub fn load_path(path: impl AsRef<Path>) -> Result<String, InputError> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => InputError::NotFound(path.to_owned()),
+        _ => InputError::Io(path.to_owned(), err),
+    })
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// The input file doesn't exist.
+    NotFound(PathBuf),
+    /// The input with the given name isn't in the input directory.
+    Missing(String, PathBuf),
+    /// The input file exists but couldn't be read.
+    Io(PathBuf, io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound(path) => write!(f, "Input file {} does not exist", path.display()),
+            InputError::Missing(name, path) => write!(
+                f,
+                "No input for {}: {} does not exist (set ${} to load inputs from another directory)",
+                name,
+                path.display(),
+                INPUT_DIR_VAR
+            ),
+            InputError::Io(path, err) => write!(f, "Failed to read {}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::NotFound(_) | InputError::Missing(_, _) => None,
+            InputError::Io(_, err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs() -> Inputs {
+        Inputs::new(concat!(env!("CARGO_MANIFEST_DIR"), "/input"))
+    }
+
+    #[test]
+    fn test_load() {
+        assert!(inputs()
+            .load(2021, "day_1_sonar_sweep")
+            .unwrap()
+            .starts_with("182\n"));
+    }
+
+    #[test]
+    fn test_load_missing() {
+        let err = inputs().load(2021, "day_26_no_such_day").unwrap_err();
+        assert!(matches!(err, InputError::Missing(_, _)));
+        assert!(err
+            .to_string()
+            .contains("2021/day_26_no_such_day.txt does not exist"));
+        assert!(matches!(
+            load_path("no_such_file.txt"),
+            Err(InputError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_path() {
+        let path = inputs().path(2021, "day_17_trick_shot");
+        assert!(path.ends_with("input/2021/day_17_trick_shot.txt"));
+        assert_eq!(
+            load_path(&path).unwrap(),
+            inputs().load(2021, "day_17_trick_shot").unwrap()
+        );
+    }
+}
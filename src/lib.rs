@@ -1,36 +1,34 @@
 #![allow(unused_imports)]
 
-pub mod day_10_syntax_scoring;
-pub mod day_11_dumbo_octopus;
-pub mod day_12_passage_pathing;
-pub mod day_13_transparent_origami;
-pub mod day_14_extended_polymerization;
-pub mod day_15_chiton;
-pub mod day_16_packet_decoder;
-pub mod day_17_trick_shot;
-pub mod day_18_snailfish;
-pub mod day_19_beacon_scanner;
-pub mod day_1_sonar_sweep;
-pub mod day_20_trench_map;
-pub mod day_2_dive;
-pub mod day_3_binary_diagnostic;
-pub mod day_4_giant_squid;
-pub mod day_5_hydrothermal_venture;
-pub mod day_6_lanternfish;
-pub mod day_7_the_treachery_of_whales;
-pub mod day_8_seven_segment_search;
-pub mod day_9_smoke_basin;
+pub mod answers;
+pub mod bench;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod report;
+pub mod scaffold;
+pub mod solution;
+pub mod year_2021;
 
+pub use error::{Error, ErrorKind, Position};
+pub use grid::{Grid, Point};
+pub use solution::{Day, Part, Solution, Timing, Year};
+
+pub(crate) use error::{expect_prefix, expect_split, parse_at, parse_int};
 pub(crate) use itertools::Itertools;
+pub(crate) use solution::{parse_comma_separated, parse_lines};
 pub(crate) use tap::{Conv, TryConv};
 
+/// Every year's solutions, in order.
+pub static YEARS: &[Year] = &[Year::new(2021, year_2021::SOLUTIONS)];
+
 pub(crate) trait Parsable<T> {
     type Err;
     fn parse(self) -> Result<T, Self::Err>;
 }
 
 impl Parsable<u8> for char {
-    type Err = String;
+    type Err = ErrorKind;
 
     fn parse(self) -> Result<u8, Self::Err> {
         match self {
@@ -50,29 +48,41 @@ impl Parsable<u8> for char {
             'D' => Ok(13),
             'E' => Ok(14),
             'F' => Ok(15),
-            _ => Err(format!("Cannot parse char {:?} as integer", self)),
+            _ => Err(ErrorKind::UnexpectedChar {
+                found: self,
+                expected: "a digit",
+            }),
         }
     }
 }
 
 // Borrowed this macro from iliana:
 // https://github.com/iliana/aoc2021/blob/d5d7eb7336b9078081a9f7a44ce7ebb6dce374f4/src/lib.rs
+// Inputs are loaded when the macro runs (see `input::Inputs::from_env`), so a
+// missing input fails the test using it rather than the whole build.
 #[macro_export]
 macro_rules! input {
-    ($day:expr) => {{
-        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/", $day, ".txt",))
+    ($year:expr, $day:expr) => {{
+        match $crate::input::Inputs::from_env().load($year, $day) {
+            Ok(input) => input,
+            Err(err) => panic!("{}", err),
+        }
     }};
 
-    ($day:expr, $ty:ty) => {{
+    ($year:expr, $day:expr, $ty:ty) => {{
         use std::str::FromStr;
-        input!($day)
+        input!($year, $day)
             .lines()
             .map(|line| <$ty>::from_str(line).unwrap())
+            .collect::<Vec<_>>()
+            .into_iter()
     }};
 
-    ($day:expr, $ty:ty, $split:expr) => {
-        input!($day)
+    ($year:expr, $day:expr, $ty:ty, $split:expr) => {
+        input!($year, $day)
             .split($split)
             .map(|line| <$ty>::from_str(line).unwrap())
+            .collect::<Vec<_>>()
+            .into_iter()
     };
 }
@@ -0,0 +1,339 @@
+use std::collections::BTreeSet;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::{env, io, process};
+
+use aoc::answers::{self, Answers, Status};
+use aoc::input::{self, InputError, Inputs};
+use aoc::report::{Format, Report, Row, SortBy};
+use aoc::scaffold::Scaffold;
+use aoc::{solution, Day, Part};
+
+const USAGE: &str = "\
+Usage: aoc [OPTIONS] <DAY> <PART> [INPUT]
+       aoc [OPTIONS] --all
+       aoc [OPTIONS] --check [DAY...]
+       aoc [OPTIONS] --new <DAY> <TITLE>
+
+Runs part 1 or 2 of the given day and prints the answer. INPUT is a path to
+the puzzle input, or `-` to read it from stdin. If it's not given, the day's
+input is loaded from the input directory.
+
+Options:
+    --year <YEAR>       Use days from YEAR. Defaults to the latest year, or to
+                        every year for --all, and for --check without days.
+    --all               Run both parts of every day.
+    --check             Check both parts of every day (or the given days)
+                        against the answers manifest, and report whether
+                        each passes, fails, or has no answer recorded.
+    --answers <FILE>    The answers manifest for --check. Defaults to
+                        `answers.tsv` in the input directory.
+    --new               Start a new day from src/_template.rs, named after
+                        the puzzle's TITLE, with an empty input file. With
+                        --year, this starts the year too if it's new.
+    --input-dir <DIR>   Load inputs from DIR. Defaults to $AOC_INPUT_DIR if
+                        it's set, or `input/` in this repository otherwise.
+    --times             Print a table of answers with parse and solve times,
+                        and their total.
+    --sort <COLUMN>     Sort the table by `day` (the default), or by `parse`,
+                        `solve`, or `total` time, slowest first. Implies
+                        --times.
+    --format <FORMAT>   Print the table as a `table` (the default), `json`, or
+                        `csv`. Implies --times.
+    --help              Print this message.";
+
+/// Parsed command-line arguments.
+#[derive(Default)]
+struct Args {
+    year: Option<u16>,
+    all: bool,
+    check: bool,
+    answers: Option<PathBuf>,
+    new: bool,
+    inputs: Option<Inputs>,
+    /// Set if the answers should be printed in a report with their times.
+    report: Option<(SortBy, Format)>,
+    positional: Vec<String>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut ret = Self::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--year" => {
+                    let year = args
+                        .next()
+                        .ok_or_else(|| "--year requires a year".to_owned())?;
+                    ret.year = Some(
+                        year.parse()
+                            .map_err(|_| format!("Expected a year, got {:?}", year))?,
+                    );
+                }
+                "--all" => ret.all = true,
+                "--check" => ret.check = true,
+                "--new" => ret.new = true,
+                "--answers" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| "--answers requires a file".to_owned())?;
+                    ret.answers = Some(path.into());
+                }
+                "--input-dir" => {
+                    let dir = args
+                        .next()
+                        .ok_or_else(|| "--input-dir requires a directory".to_owned())?;
+                    ret.inputs = Some(Inputs::new(dir));
+                }
+                "--times" => {
+                    ret.report.get_or_insert((SortBy::Day, Format::Table));
+                }
+                "--sort" => {
+                    let sort = args
+                        .next()
+                        .ok_or_else(|| "--sort requires a column".to_owned())?;
+                    ret.report.get_or_insert((SortBy::Day, Format::Table)).0 = sort.parse()?;
+                }
+                "--format" => {
+                    let format = args
+                        .next()
+                        .ok_or_else(|| "--format requires a format".to_owned())?;
+                    ret.report.get_or_insert((SortBy::Day, Format::Table)).1 = format.parse()?;
+                }
+                _ if arg.starts_with("--") => return Err(format!("Unknown option {:?}", arg)),
+                _ => ret.positional.push(arg),
+            }
+        }
+        Ok(ret)
+    }
+
+    fn inputs(&self) -> Inputs {
+        self.inputs.clone().unwrap_or_else(Inputs::from_env)
+    }
+
+    /// The given year, or the latest one.
+    fn year(&self) -> u16 {
+        self.year.unwrap_or_else(|| solution::latest_year().year)
+    }
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    day.parse()
+        .map_err(|_| format!("Expected a day number, got {:?}", day))
+}
+
+fn print_answer(row: &Row) {
+    let answer = &row.timing.answer;
+    if answer.contains('\n') {
+        // Multi-line answers (like day 13's ASCII art) read better on their
+        // own lines.
+        print!(
+            "{} day {} part {}:\n{}",
+            row.year, row.day, row.part, answer
+        );
+    } else {
+        println!("{} day {} part {}: {}", row.year, row.day, row.part, answer);
+    }
+}
+
+fn time(solution: &Day, input: &str, part: Part) -> Result<Row, String> {
+    Ok(Row {
+        year: solution.year,
+        day: solution.day,
+        part,
+        timing: solution
+            .time(input, part)
+            .map_err(|err| format!("Bad input for {}: {}", solution, err))?,
+    })
+}
+
+/// Print the answers as they're found, or all at once in a report.
+fn print_rows(rows: impl Iterator<Item = Result<Row, String>>, args: &Args) -> Result<(), String> {
+    match args.report {
+        Some((sort, format)) => {
+            let mut report = Report::default();
+            for row in rows {
+                report.push(row?);
+            }
+            report.sort(sort);
+            report
+                .write(format, &mut io::stdout().lock())
+                .map_err(|err| format!("Failed to write report: {}", err))
+        }
+        None => {
+            for row in rows {
+                let row = row?;
+                if args.all {
+                    print_answer(&row);
+                } else {
+                    println!("{}", row.timing.answer.trim_end());
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+fn read_stdin() -> Result<String, String> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|err| format!("Failed to read stdin: {}", err))?;
+    Ok(input)
+}
+
+fn run_all(args: &Args) -> Result<(), String> {
+    let inputs = args.inputs();
+    let rows = solution::select(args.year, &[])?
+        .into_iter()
+        .flat_map(|solution| {
+            let input = inputs
+                .load(solution.year, solution.name)
+                .map_err(|err| err.to_string());
+            Part::ALL.into_iter().map(move |part| {
+                input
+                    .as_ref()
+                    .map_err(Clone::clone)
+                    .and_then(|input| time(solution, input, part))
+            })
+        });
+    print_rows(rows, args)
+}
+
+fn run_check(args: &Args, days: &[String]) -> Result<(), String> {
+    let inputs = args.inputs();
+    let path = args
+        .answers
+        .clone()
+        .unwrap_or_else(|| Answers::path(&inputs));
+    let answers = Answers::load(&path)
+        .map_err(|err| err.to_string())?
+        .unwrap_or_default();
+    let days = days
+        .iter()
+        .map(|day| parse_day(day))
+        .collect::<Result<Vec<_>, _>>()?;
+    let days = solution::select(args.year, &days)?;
+
+    // Each day's own input, and any others with answers in the manifest.
+    let mut runs = BTreeSet::new();
+    for solution in &days {
+        for part in Part::ALL {
+            runs.insert((
+                solution.year,
+                solution.day,
+                format!("{}.txt", solution.name),
+                part,
+            ));
+        }
+    }
+    for answer in answers.iter() {
+        if days
+            .iter()
+            .any(|solution| (solution.year, solution.day) == (answer.year, answer.day))
+        {
+            runs.insert((answer.year, answer.day, answer.input.clone(), answer.part));
+        }
+    }
+
+    let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+    for (year, day, file, part) in runs {
+        let solution = solution::find(year, day).expect("Only registered days are run");
+        let known = answers.get(year, day, part, &file).is_some();
+        let actual = match input::load_path(inputs.year_dir(year).join(&file)) {
+            // It's fine for a day's own input to be missing if there's no
+            // answer to check.
+            Err(InputError::NotFound(_)) if !known => continue,
+            Err(err) => Err(err.to_string()),
+            Ok(input) => solution
+                .solve(&input, part)
+                .map_err(|err| format!("Bad input for {}: {}", solution, err)),
+        };
+        let label = format!("{} part {} ({})", solution, part, file);
+        match actual {
+            Err(err) => {
+                failed += 1;
+                println!("{}: FAIL: {}", label, err);
+            }
+            Ok(actual) => match answers.check(year, day, part, &file, &actual) {
+                Status::Pass => {
+                    passed += 1;
+                    println!("{}: pass", label);
+                }
+                Status::Fail { expected } => {
+                    failed += 1;
+                    println!(
+                        "{}: FAIL: expected {}, got {}",
+                        label,
+                        answers::escape(&expected),
+                        answers::escape(&actual)
+                    );
+                }
+                Status::Unknown => {
+                    unknown += 1;
+                    println!("{}: unknown, got {}", label, answers::escape(&actual));
+                }
+            },
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} unknown",
+        passed, failed, unknown
+    );
+    if failed > 0 {
+        return Err(format!("Some answers don't match {}", path.display()));
+    }
+    Ok(())
+}
+
+fn run_new(args: &Args, day: &str, title: &[String]) -> Result<(), String> {
+    let scaffold = Scaffold::new(args.year(), parse_day(day)?, &title.join(" "))?;
+    for path in scaffold.create(Path::new(env!("CARGO_MANIFEST_DIR")), &args.inputs())? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    if args.new {
+        return match args.positional.as_slice() {
+            [day, title @ ..] if !title.is_empty() && !args.all && !args.check => {
+                run_new(args, day, title)
+            }
+            _ => Err(USAGE.to_owned()),
+        };
+    }
+    match args.positional.as_slice() {
+        days if args.check && !args.all && args.report.is_none() => run_check(args, days),
+        [] if args.all && !args.check => run_all(args),
+        [day, part, rest @ ..] if !args.all && !args.check && rest.len() <= 1 => {
+            let day = parse_day(day)?;
+            let solution = solution::find(args.year(), day)
+                .ok_or_else(|| format!("No solution for {} day {}", args.year(), day))?;
+            let part = part.parse()?;
+            let input = match rest.first().map(String::as_str) {
+                Some("-") => read_stdin()?,
+                Some(path) => input::load_path(path).map_err(|err| err.to_string())?,
+                None => args
+                    .inputs()
+                    .load(solution.year, solution.name)
+                    .map_err(|err| err.to_string())?,
+            };
+            print_rows(std::iter::once(time(solution, &input, part)), args)
+        }
+        _ => Err(USAGE.to_owned()),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return;
+    }
+    if let Err(err) = Args::parse(args.into_iter()).and_then(|args| run(&args)) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,353 @@
+//! A summary of answers and how long they took, as printed by the runner's
+//! `--times` option.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::bench::format_duration;
+use crate::*;
+
+/// One part of one day.
+#[derive(Clone, Debug)]
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub timing: Timing,
+}
+
+/// The column to sort a [`Report`] by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortBy {
+    /// By year, day and part, which is the order they're run in.
+    Day,
+    /// Times sort slowest first.
+    Parse,
+    Solve,
+    Total,
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(SortBy::Day),
+            "parse" => Ok(SortBy::Parse),
+            "solve" => Ok(SortBy::Solve),
+            "total" => Ok(SortBy::Total),
+            _ => Err(format!(
+                "Sort column must be day, parse, solve, or total, got {:?}",
+                s
+            )),
+        }
+    }
+}
+
+/// How to print a [`Report`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Aligned columns for reading, with a total at the bottom.
+    Table,
+    /// Times are in nanoseconds.
+    Json,
+    /// Times are in nanoseconds. There's no total row, so that every row has
+    /// the same shape.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Format must be table, json, or csv, got {:?}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    rows: Vec<Row>,
+}
+
+impl Report {
+    pub fn push(&mut self, row: Row) {
+        self.rows.push(row);
+    }
+
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    pub fn sort(&mut self, by: SortBy) {
+        match by {
+            SortBy::Day => self.rows.sort_by_key(|row| (row.year, row.day, row.part)),
+            SortBy::Parse => self
+                .rows
+                .sort_by_key(|row| std::cmp::Reverse(row.timing.parse)),
+            SortBy::Solve => self
+                .rows
+                .sort_by_key(|row| std::cmp::Reverse(row.timing.solve)),
+            SortBy::Total => self
+                .rows
+                .sort_by_key(|row| std::cmp::Reverse(row.timing.total())),
+        }
+    }
+
+    /// Total parse and solve times.
+    pub fn total(&self) -> (Duration, Duration) {
+        self.rows
+            .iter()
+            .fold((Duration::ZERO, Duration::ZERO), |(parse, solve), row| {
+                (parse + row.timing.parse, solve + row.timing.solve)
+            })
+    }
+
+    pub fn write(&self, format: Format, w: &mut impl Write) -> io::Result<()> {
+        match format {
+            Format::Table => self.write_table(w),
+            Format::Json => self.write_json(w),
+            Format::Csv => self.write_csv(w),
+        }
+    }
+
+    fn write_table(&self, w: &mut impl Write) -> io::Result<()> {
+        // Multi-line answers (like day 13's ASCII art) are printed after the
+        // table instead of in it.
+        let cell = |row: &Row| {
+            if row.timing.answer.contains('\n') {
+                "(below)".to_owned()
+            } else {
+                row.timing.answer.clone()
+            }
+        };
+        let width = self
+            .rows
+            .iter()
+            .map(|row| cell(row).chars().count())
+            .chain(["answer".len()])
+            .max()
+            .unwrap_or_default();
+
+        writeln!(
+            w,
+            "year  day  part  {:<width$}  {:>9}  {:>9}  {:>9}",
+            "answer",
+            "parse",
+            "solve",
+            "total",
+            width = width
+        )?;
+        for row in &self.rows {
+            writeln!(
+                w,
+                "{:>4}  {:>3}  {:>4}  {:<width$}  {:>9}  {:>9}  {:>9}",
+                row.year,
+                row.day,
+                row.part.to_string(),
+                cell(row),
+                format_duration(row.timing.parse),
+                format_duration(row.timing.solve),
+                format_duration(row.timing.total()),
+                width = width
+            )?;
+        }
+        let (parse, solve) = self.total();
+        writeln!(
+            w,
+            "{:<width$}  {:>9}  {:>9}  {:>9}",
+            "total",
+            format_duration(parse),
+            format_duration(solve),
+            format_duration(parse + solve),
+            width = "year  day  part  ".len() + width
+        )?;
+
+        for row in &self.rows {
+            if row.timing.answer.contains('\n') {
+                write!(
+                    w,
+                    "\n{} day {} part {}:\n{}",
+                    row.year, row.day, row.part, row.timing.answer
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_json(&self, w: &mut impl Write) -> io::Result<()> {
+        writeln!(w, "{{")?;
+        writeln!(w, "  \"rows\": [")?;
+        for (i, row) in self.rows.iter().enumerate() {
+            writeln!(
+                w,
+                "    {{\"year\": {}, \"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}, \"total_ns\": {}}}{}",
+                row.year,
+                row.day,
+                row.part,
+                json_string(&row.timing.answer),
+                row.timing.parse.as_nanos(),
+                row.timing.solve.as_nanos(),
+                row.timing.total().as_nanos(),
+                if i + 1 < self.rows.len() { "," } else { "" }
+            )?;
+        }
+        writeln!(w, "  ],")?;
+        let (parse, solve) = self.total();
+        writeln!(
+            w,
+            "  \"total\": {{\"parse_ns\": {}, \"solve_ns\": {}, \"total_ns\": {}}}",
+            parse.as_nanos(),
+            solve.as_nanos(),
+            (parse + solve).as_nanos()
+        )?;
+        writeln!(w, "}}")
+    }
+
+    fn write_csv(&self, w: &mut impl Write) -> io::Result<()> {
+        writeln!(w, "year,day,part,answer,parse_ns,solve_ns,total_ns")?;
+        for row in &self.rows {
+            writeln!(
+                w,
+                "{},{},{},{},{},{},{}",
+                row.year,
+                row.day,
+                row.part,
+                csv_field(&row.timing.answer),
+                row.timing.parse.as_nanos(),
+                row.timing.solve.as_nanos(),
+                row.timing.total().as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Quote and escape `s` as a JSON string.
+fn json_string(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len() + 2);
+    ret.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            _ if c.is_control() => write!(ret, "\\u{:04x}", c as u32).unwrap(),
+            _ => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+/// Quote `s` as a CSV field if it needs it.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Report {
+        let row = |day, part, answer: &str, parse, solve| Row {
+            year: 2021,
+            day,
+            part,
+            timing: Timing {
+                answer: answer.to_owned(),
+                parse: Duration::from_micros(parse),
+                solve: Duration::from_micros(solve),
+            },
+        };
+        let mut report = Report::default();
+        report.push(row(1, Part::One, "1602", 60, 2));
+        report.push(row(1, Part::Two, "1633", 60, 5));
+        report.push(row(13, Part::Two, "#.\n.#\n", 100, 300));
+        report
+    }
+
+    fn write(report: &Report, format: Format) -> String {
+        let mut out = Vec::new();
+        report.write(format, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut report = sample();
+        let order = |report: &Report| {
+            report
+                .rows()
+                .iter()
+                .map(|row| (row.day, row.part))
+                .collect::<Vec<_>>()
+        };
+        report.sort(SortBy::Solve);
+        assert_eq!(
+            order(&report),
+            [(13, Part::Two), (1, Part::Two), (1, Part::One)]
+        );
+        report.sort(SortBy::Day);
+        assert_eq!(
+            order(&report),
+            [(1, Part::One), (1, Part::Two), (13, Part::Two)]
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        assert_eq!(
+            write(&sample(), Format::Table),
+            "year  day  part  answer       parse      solve      total\n\
+             2021    1     1  1602        60.0µs      2.0µs     62.0µs\n\
+             2021    1     2  1633        60.0µs      5.0µs     65.0µs\n\
+             2021   13     2  (below)    100.0µs    300.0µs    400.0µs\n\
+             total                       220.0µs    307.0µs    527.0µs\n\
+             \n\
+             2021 day 13 part 2:\n\
+             #.\n\
+             .#\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            write(&sample(), Format::Json),
+            r##"{
+  "rows": [
+    {"year": 2021, "day": 1, "part": 1, "answer": "1602", "parse_ns": 60000, "solve_ns": 2000, "total_ns": 62000},
+    {"year": 2021, "day": 1, "part": 2, "answer": "1633", "parse_ns": 60000, "solve_ns": 5000, "total_ns": 65000},
+    {"year": 2021, "day": 13, "part": 2, "answer": "#.\n.#\n", "parse_ns": 100000, "solve_ns": 300000, "total_ns": 400000}
+  ],
+  "total": {"parse_ns": 220000, "solve_ns": 307000, "total_ns": 527000}
+}
+"##
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            write(&sample(), Format::Csv),
+            "year,day,part,answer,parse_ns,solve_ns,total_ns\n\
+             2021,1,1,1602,60000,2000,62000\n\
+             2021,1,2,1633,60000,5000,65000\n\
+             2021,13,2,\"#.\n.#\n\",100000,300000,400000\n"
+        );
+        assert_eq!(csv_field("a \"b\", c"), "\"a \"\"b\"\", c\"");
+    }
+}
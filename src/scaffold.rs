@@ -0,0 +1,423 @@
+//! Generating a new day from `src/_template.rs`, and a new year for it if
+//! needed. Run with `aoc --year <YEAR> --new <DAY> <TITLE>`.
+
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use crate::input::Inputs;
+
+const TEMPLATE: &str = include_str!("_template.rs");
+
+/// The parts of the template that are renamed for each day.
+const TEMPLATE_NAME: &str = "\"_template\"";
+const TEMPLATE_STRUCT: &str = "Template";
+const TEMPLATE_YEAR: &str = "const YEAR: u16 = 0;";
+const TEMPLATE_DAY: &str = "const DAY: u8 = 0;";
+const TEMPLATE_INPUT: &str = "input!(0, ";
+
+/// Where the registry of every day's solution starts in a year's `mod.rs`.
+const DAYS_START: &str = "pub static SOLUTIONS: &[Day] = &[";
+/// Where the registry of every year starts in `lib.rs`.
+const YEARS_START: &str = "pub static YEARS: &[Year] = &[";
+
+/// A new day, named after its puzzle's title.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scaffold {
+    pub year: u16,
+    pub day: u8,
+    /// The module name, like `day_1_sonar_sweep`. This is also the name of
+    /// the day's input.
+    pub module: String,
+    /// The name of the type implementing [`Solution`](crate::Solution), like
+    /// `SonarSweep`.
+    pub solution: String,
+}
+
+impl Scaffold {
+    /// Name a new day after its puzzle's title, like `"Sonar Sweep"`.
+    pub fn new(year: u16, day: u8, title: &str) -> Result<Self, String> {
+        if year < 2015 {
+            return Err(format!("Advent of Code started in 2015, got {}", year));
+        }
+        if !(1..=25).contains(&day) {
+            return Err(format!("Expected a day from 1 to 25, got {}", day));
+        }
+        let words: Vec<String> = title
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect();
+        if words.is_empty() {
+            return Err(format!("Expected a puzzle title, got {:?}", title));
+        }
+        let mut solution = words
+            .iter()
+            .map(|word| word[..1].to_ascii_uppercase() + &word[1..])
+            .collect::<String>();
+        // Type names can't start with a digit, like in "1202 Program Alarm".
+        if solution.starts_with(|c: char| c.is_ascii_digit()) {
+            solution.insert_str(0, "Day");
+        }
+        Ok(Self {
+            year,
+            day,
+            module: format!("day_{}_{}", day, words.join("_")),
+            solution,
+        })
+    }
+
+    /// The year's module, like `year_2021`.
+    pub fn year_module(&self) -> String {
+        format!("year_{}", self.year)
+    }
+
+    /// The new day's source, from the template.
+    pub fn render(&self) -> String {
+        TEMPLATE
+            .replace(TEMPLATE_NAME, &format!("{:?}", self.module))
+            .replace(TEMPLATE_STRUCT, &self.solution)
+            .replace(TEMPLATE_YEAR, &format!("const YEAR: u16 = {};", self.year))
+            .replace(TEMPLATE_DAY, &format!("const DAY: u8 = {};", self.day))
+            .replace(TEMPLATE_INPUT, &format!("input!({}, ", self.year))
+    }
+
+    /// The `mod.rs` for a new year, with this as its only day.
+    pub fn render_year(&self) -> String {
+        format!(
+            "//! Advent of Code {}.\n\
+             \n\
+             use crate::Day;\n\
+             \n\
+             pub mod {};\n\
+             \n\
+             /// Every day's solution, in order.\n\
+             {}\n    \
+             Day::new::<{}::{}>(),\n\
+             ];\n",
+            self.year, self.module, DAYS_START, self.module, self.solution
+        )
+    }
+
+    /// Add the new day to its year's `mod.rs`: to the `pub mod`
+    /// declarations, which are kept sorted, and to the registry, which is in
+    /// day order.
+    pub fn register_day(&self, year_mod: &str) -> Result<String, String> {
+        let mut lines = year_mod.lines().map(str::to_owned).collect();
+        let entry = format!("Day::new::<{}::{}>()", self.module, self.solution);
+        add_entry(&mut lines, DAYS_START, entry, |entry| {
+            day_of(entry.strip_prefix("Day::new::<")?).map(u16::from)
+        })
+        .map_err(|err| format!("{} in {}'s mod.rs", err, self.year))?;
+        add_mod(&mut lines, &self.module)?;
+        Ok(lines.join("\n") + "\n")
+    }
+
+    /// Add the new year to `lib.rs`, like [`Scaffold::register_day`].
+    pub fn register_year(&self, lib_rs: &str) -> Result<String, String> {
+        let mut lines = lib_rs.lines().map(str::to_owned).collect();
+        let module = self.year_module();
+        let entry = format!("Year::new({}, {}::SOLUTIONS)", self.year, module);
+        add_entry(&mut lines, YEARS_START, entry, |entry| {
+            entry
+                .strip_prefix("Year::new(")?
+                .split(',')
+                .next()?
+                .parse()
+                .ok()
+        })
+        .map_err(|err| format!("{} in lib.rs", err))?;
+        add_mod(&mut lines, &module)?;
+        Ok(lines.join("\n") + "\n")
+    }
+
+    /// Create the new day's source file and empty input, and register it in
+    /// its year, starting the year if it's new. `root` is the root of the
+    /// repository.
+    ///
+    /// Returns the paths that were written.
+    pub fn create(&self, root: &Path, inputs: &Inputs) -> Result<Vec<PathBuf>, String> {
+        let src = root.join("src");
+        let year_dir = src.join(self.year_module());
+        let source = year_dir.join(&self.module).with_extension("rs");
+        if source.exists() {
+            return Err(format!("{} already exists", source.display()));
+        }
+
+        // Work out every change before writing any of them.
+        let mut writes = vec![(source, self.render())];
+        let year_mod = year_dir.join("mod.rs");
+        if year_mod.exists() {
+            let registered = self.register_day(&read(&year_mod)?)?;
+            writes.push((year_mod, registered));
+        } else {
+            let lib_rs = src.join("lib.rs");
+            let registered = self.register_year(&read(&lib_rs)?)?;
+            writes.push((year_mod, self.render_year()));
+            writes.push((lib_rs, registered));
+        }
+        let input = inputs.path(self.year, &self.module);
+        if !input.exists() {
+            writes.push((input, String::new()));
+        }
+
+        let mut ret = Vec::with_capacity(writes.len());
+        for (path, contents) in writes {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|err| write_error(dir, err))?;
+            }
+            fs::write(&path, contents).map_err(|err| write_error(&path, err))?;
+            ret.push(path);
+        }
+        Ok(ret)
+    }
+}
+
+/// Add `pub mod module;` to the `pub mod` declarations in `lines`, which are
+/// kept sorted.
+fn add_mod(lines: &mut Vec<String>, module: &str) -> Result<(), String> {
+    let mods: Vec<(usize, &str)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, line.strip_prefix("pub mod ")?.strip_suffix(';')?)))
+        .collect();
+    let at = match mods.iter().find(|(_, existing)| *existing > module) {
+        Some(&(i, _)) => i,
+        None => mods.last().ok_or("Couldn't find any modules")?.0 + 1,
+    };
+    lines.insert(at, format!("pub mod {};", module));
+    Ok(())
+}
+
+/// Add `entry` to the static list that starts with `start` in `lines`,
+/// keeping the list sorted by `key`, and format it with one entry per line.
+fn add_entry(
+    lines: &mut Vec<String>,
+    start: &str,
+    entry: String,
+    key: impl Fn(&str) -> Option<u16>,
+) -> Result<(), String> {
+    let first = lines
+        .iter()
+        .position(|line| line.starts_with(start))
+        .ok_or_else(|| format!("Couldn't find {:?}", start))?;
+    let last = first
+        + lines[first..]
+            .iter()
+            .position(|line| line.ends_with("];"))
+            .ok_or_else(|| format!("Couldn't find the end of {:?}", start))?;
+    let list = lines[first..=last].join("\n");
+    let list = &list[start.len()..list.len() - "];".len()];
+
+    let mut entries = Vec::new();
+    for existing in split_list(list) {
+        let existing_key =
+            key(&existing).ok_or_else(|| format!("Unexpected entry {:?}", existing))?;
+        entries.push((existing_key, existing));
+    }
+    let new_key = key(&entry).expect("New entries should have a key");
+    if let Some((_, existing)) = entries.iter().find(|(key, _)| *key == new_key) {
+        return Err(format!("{} is already registered", existing));
+    }
+    entries.push((new_key, entry));
+    entries.sort();
+
+    let list = std::iter::once(start.to_owned())
+        .chain(
+            entries
+                .into_iter()
+                .map(|(_, entry)| format!("    {},", entry)),
+        )
+        .chain(std::iter::once("];".to_owned()));
+    lines.splice(first..=last, list);
+    Ok(())
+}
+
+/// Split the comma-separated entries of a list, ignoring commas nested in
+/// brackets.
+fn split_list(list: &str) -> Vec<String> {
+    let mut ret = Vec::new();
+    let mut depth = 0;
+    let mut entry = String::new();
+    for c in list.chars() {
+        match c {
+            '(' | '[' | '<' => depth += 1,
+            ')' | ']' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                ret.push(std::mem::take(&mut entry));
+                continue;
+            }
+            _ => {}
+        }
+        entry.push(c);
+    }
+    ret.push(entry);
+    ret.into_iter()
+        .map(|entry| entry.trim().to_owned())
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+/// The day number in a module name like `day_1_sonar_sweep`.
+fn day_of(module: &str) -> Option<u8> {
+    module.strip_prefix("day_")?.split('_').next()?.parse().ok()
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("Failed to read {}: {}", path.display(), err))
+}
+
+fn write_error(path: &Path, err: io::Error) -> String {
+    format!("Failed to write {}: {}", path.display(), err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Scaffold {
+        Scaffold::new(2021, 21, "Dirac Dice").unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            sample(),
+            Scaffold {
+                year: 2021,
+                day: 21,
+                module: "day_21_dirac_dice".to_owned(),
+                solution: "DiracDice".to_owned(),
+            }
+        );
+        let whales = Scaffold::new(2021, 7, "The Treachery of Whales").unwrap();
+        assert_eq!(whales.module, "day_7_the_treachery_of_whales");
+        assert_eq!(whales.solution, "TheTreacheryOfWhales");
+        assert!(Scaffold::new(2021, 0, "Dirac Dice").is_err());
+        assert!(Scaffold::new(21, 21, "Dirac Dice").is_err());
+        assert!(Scaffold::new(2021, 21, " -- ").is_err());
+        assert_eq!(
+            Scaffold::new(2021, 2, "1202 Program Alarm").unwrap(),
+            Scaffold {
+                year: 2021,
+                day: 2,
+                module: "day_2_1202_program_alarm".to_owned(),
+                solution: "Day1202ProgramAlarm".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let source = sample().render();
+        assert!(source.contains("pub struct DiracDice;"));
+        assert!(source.contains("impl Solution for DiracDice {"));
+        assert!(source.contains("const YEAR: u16 = 2021;"));
+        assert!(source.contains("const DAY: u8 = 21;"));
+        assert!(source.contains("const NAME: &'static str = \"day_21_dirac_dice\";"));
+        assert!(source.contains("DiracDice::parse(&input!(2021, \"day_21_dirac_dice\"))"));
+        assert!(!source.contains("emplate"));
+    }
+
+    #[test]
+    fn test_register_day() {
+        let year_mod = "\
+use crate::Day;
+
+pub mod day_1_sonar_sweep;
+pub mod day_2_dive;
+
+/// Every day's solution, in order.
+pub static SOLUTIONS: &[Day] = &[
+    Day::new::<day_1_sonar_sweep::SonarSweep>(),
+    Day::new::<day_2_dive::Dive>(),
+];
+";
+        assert_eq!(
+            sample().register_day(year_mod).unwrap(),
+            "\
+use crate::Day;
+
+pub mod day_1_sonar_sweep;
+pub mod day_21_dirac_dice;
+pub mod day_2_dive;
+
+/// Every day's solution, in order.
+pub static SOLUTIONS: &[Day] = &[
+    Day::new::<day_1_sonar_sweep::SonarSweep>(),
+    Day::new::<day_2_dive::Dive>(),
+    Day::new::<day_21_dirac_dice::DiracDice>(),
+];
+"
+        );
+        assert_eq!(
+            Scaffold::new(2021, 2, "Dive")
+                .unwrap()
+                .register_day(year_mod),
+            Err("Day::new::<day_2_dive::Dive>() is already registered in 2021's mod.rs".to_owned())
+        );
+        assert!(sample()
+            .register_day("pub mod day_1_sonar_sweep;\n")
+            .is_err());
+    }
+
+    #[test]
+    fn test_register_day_2021() {
+        let registered = sample()
+            .register_day(include_str!("year_2021/mod.rs"))
+            .unwrap();
+        assert!(registered.contains("\npub mod day_21_dirac_dice;\npub mod day_2_dive;\n"));
+        assert!(registered.contains(
+            "    Day::new::<day_20_trench_map::TrenchMap>(),\n    \
+             Day::new::<day_21_dirac_dice::DiracDice>(),\n];\n"
+        ));
+    }
+
+    #[test]
+    fn test_new_year() {
+        let scaffold = Scaffold::new(2022, 1, "Calorie Counting").unwrap();
+        assert_eq!(
+            scaffold.render_year(),
+            "\
+//! Advent of Code 2022.
+
+use crate::Day;
+
+pub mod day_1_calorie_counting;
+
+/// Every day's solution, in order.
+pub static SOLUTIONS: &[Day] = &[
+    Day::new::<day_1_calorie_counting::CalorieCounting>(),
+];
+"
+        );
+        // The new year's `mod.rs` can have more days added to it.
+        let year_mod = Scaffold::new(2022, 2, "Rock Paper Scissors")
+            .unwrap()
+            .register_day(&scaffold.render_year())
+            .unwrap();
+        assert!(year_mod
+            .contains("    Day::new::<day_2_rock_paper_scissors::RockPaperScissors>(),\n];"));
+
+        let registered = scaffold.register_year(include_str!("lib.rs")).unwrap();
+        assert!(registered.contains("\npub mod year_2021;\npub mod year_2022;\n"));
+        assert!(registered.contains(
+            "pub static YEARS: &[Year] = &[\n    \
+             Year::new(2021, year_2021::SOLUTIONS),\n    \
+             Year::new(2022, year_2022::SOLUTIONS),\n\
+             ];\n"
+        ));
+        assert!(sample().register_year(include_str!("lib.rs")).is_err());
+    }
+
+    #[test]
+    fn test_split_list() {
+        assert_eq!(
+            split_list("Year::new(2021, year_2021::SOLUTIONS), Year::new(2022, x),\n"),
+            [
+                "Year::new(2021, year_2021::SOLUTIONS)",
+                "Year::new(2022, x)"
+            ]
+        );
+        assert!(split_list("\n").is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+use std::fmt::Display;
+use std::num::ParseIntError;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::*;
+
+/// A day's puzzle: how to parse its input and solve both of its parts.
+pub trait Solution {
+    /// The year of the Advent of Code event the puzzle is from.
+    const YEAR: u16;
+    /// The day of December the puzzle was released on.
+    const DAY: u8;
+    /// The name of the module, which is also the name of the input file in
+    /// the year's input directory.
+    const NAME: &'static str;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error>;
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error>;
+}
+
+/// One of the two parts of a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Part must be 1 or 2, got {:?}", s)),
+        }
+    }
+}
+
+/// The answer to one part of a puzzle, and how long it took to find.
+#[derive(Clone, Debug)]
+pub struct Timing {
+    pub answer: String,
+    /// Time spent in [`Solution::parse`].
+    pub parse: Duration,
+    /// Time spent in [`Solution::part_1`] or [`Solution::part_2`], including
+    /// formatting the answer.
+    pub solve: Duration,
+}
+
+impl Timing {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// A type-erased [`Solution`], so that days with different input and answer
+/// types can be listed together in a [`Year`].
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub name: &'static str,
+    time: fn(&str, Part) -> Result<Timing, Error>,
+}
+
+impl Day {
+    pub const fn new<S: Solution>() -> Self {
+        Self {
+            year: S::YEAR,
+            day: S::DAY,
+            name: S::NAME,
+            time: time::<S>,
+        }
+    }
+
+    /// Parse `input` and solve the given part, formatting the answer.
+    pub fn solve(&self, input: &str, part: Part) -> Result<String, Error> {
+        self.time(input, part).map(|timing| timing.answer)
+    }
+
+    /// Like [`Day::solve`], but also time parsing and solving separately.
+    pub fn time(&self, input: &str, part: Part) -> Result<Timing, Error> {
+        (self.time)(input, part)
+    }
+}
+
+impl Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+fn time<S: Solution>(input: &str, part: Part) -> Result<Timing, Error> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let answer = match part {
+        Part::One => S::part_1(input)?.to_string(),
+        Part::Two => S::part_2(input)?.to_string(),
+    };
+    let solve = start.elapsed();
+
+    Ok(Timing {
+        answer,
+        parse,
+        solve,
+    })
+}
+
+/// Every day's solution for one year, listed together in [`YEARS`].
+#[derive(Clone, Copy)]
+pub struct Year {
+    pub year: u16,
+    /// In order of day.
+    pub days: &'static [Day],
+}
+
+impl Year {
+    pub const fn new(year: u16, days: &'static [Day]) -> Self {
+        Self { year, days }
+    }
+
+    /// Look up the solution for a given day of this year.
+    pub fn find(&self, day: u8) -> Option<&'static Day> {
+        self.days.iter().find(|solution| solution.day == day)
+    }
+}
+
+/// Look up the solutions for a given year.
+pub fn find_year(year: u16) -> Option<&'static Year> {
+    YEARS.iter().find(|solutions| solutions.year == year)
+}
+
+/// Look up the solution for a given day.
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    find_year(year)?.find(day)
+}
+
+/// The most recent year with any solutions.
+pub fn latest_year() -> &'static Year {
+    YEARS.last().expect("There are no years in YEARS")
+}
+
+/// Every day of every year, in order.
+pub fn all() -> impl Iterator<Item = &'static Day> {
+    YEARS.iter().flat_map(|year| year.days)
+}
+
+/// The given `days` of `year`, or of the latest year if `year` isn't given.
+/// If no days are given, every day of `year`, or of every year.
+pub fn select(year: Option<u16>, days: &[u8]) -> Result<Vec<&'static Day>, String> {
+    let year = match year {
+        Some(year) => find_year(year).ok_or_else(|| format!("No solutions for {}", year))?,
+        None if days.is_empty() => return Ok(all().collect()),
+        None => latest_year(),
+    };
+    if days.is_empty() {
+        return Ok(year.days.iter().collect());
+    }
+    days.iter()
+        .map(|&day| {
+            year.find(day)
+                .ok_or_else(|| format!("No solution for {} day {}", year.year, day))
+        })
+        .collect()
+}
+
+/// Parse each line of `input` as a `T`.
+pub(crate) fn parse_lines<T>(input: &str) -> Result<Vec<T>, Error>
+where
+    T: FromStr<Err = Error>,
+{
+    input.lines().map(|line| parse_at(input, line)).collect()
+}
+
+/// Parse a single line of comma-separated integers.
+pub(crate) fn parse_comma_separated<T>(input: &str) -> Result<Vec<T>, Error>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    input
+        .trim_end()
+        .split(',')
+        .map(|item| parse_int(input, item))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solutions_in_order() {
+        assert!(YEARS.windows(2).all(|years| years[0].year < years[1].year));
+        for year in YEARS {
+            assert!(year.days.windows(2).all(|days| days[0].day < days[1].day));
+            for solution in year.days {
+                assert_eq!(solution.year, year.year);
+                assert!(solution.name.starts_with(&format!("day_{}_", solution.day)));
+            }
+        }
+        assert_eq!(find_year(2021).unwrap().days.len(), 20);
+    }
+
+    #[test]
+    fn test_solve() {
+        let solution = find(2021, 1).unwrap();
+        let sample = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        assert_eq!(solution.solve(sample, Part::One).unwrap(), "7");
+        assert_eq!(solution.solve(sample, Part::Two).unwrap(), "5");
+        assert_eq!(
+            solution
+                .solve("199\nbees\n", Part::One)
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: unexpected character 'b', expected a digit"
+        );
+    }
+}
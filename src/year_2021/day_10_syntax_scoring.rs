@@ -0,0 +1,247 @@
+use crate::*;
+
+#[derive(Debug, PartialEq)]
+pub enum Type {
+    Round,
+    Square,
+    Curly,
+    Angle,
+}
+
+#[derive(Debug)]
+pub enum Chunk {
+    Open,
+    Close,
+}
+
+pub type Token = (Chunk, Type);
+
+use Chunk::*;
+use Type::*;
+
+/// Tokenize one line of the navigation subsystem. Error positions are
+/// relative to `line`.
+pub fn tokenize(line: &str) -> Result<Vec<Token>, Error> {
+    line.char_indices()
+        .map(|(i, c)| match c {
+            '(' => Ok((Open, Round)),
+            ')' => Ok((Close, Round)),
+            '[' => Ok((Open, Square)),
+            ']' => Ok((Close, Square)),
+            '{' => Ok((Open, Curly)),
+            '}' => Ok((Close, Curly)),
+            '<' => Ok((Open, Angle)),
+            '>' => Ok((Close, Angle)),
+            _ => Err(Error::at(
+                line,
+                &line[i..],
+                ErrorKind::UnexpectedChar {
+                    found: c,
+                    expected: "one of ()[]{}<>",
+                },
+            )),
+        })
+        .collect()
+}
+
+enum LineAnalysis {
+    Corrupted(Type),
+    Incomplete(Vec<Type>),
+}
+
+/// Analyze the `index`th line (counting from 0), which is used to report
+/// where a closing token without an opening token is.
+fn analyze(index: usize, line: impl Iterator<Item = Token>) -> Result<LineAnalysis, Error> {
+    let mut open_chunks = Vec::new();
+    for (column, (chunk, ty)) in line.enumerate() {
+        match chunk {
+            Open => open_chunks.push(ty),
+            Close => match open_chunks.pop() {
+                Some(expected_ty) => {
+                    if ty != expected_ty {
+                        return Ok(LineAnalysis::Corrupted(ty));
+                    }
+                }
+                None => {
+                    return Err(Error::at_position(
+                        Position {
+                            line: index + 1,
+                            column: column + 1,
+                        },
+                        ErrorKind::UnexpectedChar {
+                            found: match ty {
+                                Round => ')',
+                                Square => ']',
+                                Curly => '}',
+                                Angle => '>',
+                            },
+                            expected: "a closing token to have an opening token",
+                        },
+                    ))
+                }
+            },
+        }
+    }
+    open_chunks.reverse();
+    Ok(LineAnalysis::Incomplete(open_chunks))
+}
+
+pub fn part_1<Outer, Inner>(lines: Outer) -> Result<usize, Error>
+where
+    Outer: Iterator<Item = Inner>,
+    Inner: Iterator<Item = Token>,
+{
+    lines
+        .enumerate()
+        .map(|(i, line)| {
+            Ok(match analyze(i, line)? {
+                LineAnalysis::Corrupted(ty) => match ty {
+                    Round => 3,
+                    Square => 57,
+                    Curly => 1197,
+                    Angle => 25137,
+                },
+                LineAnalysis::Incomplete(_) => 0,
+            })
+        })
+        .sum()
+}
+
+pub fn part_2<Outer, Inner>(lines: Outer) -> Result<usize, Error>
+where
+    Outer: Iterator<Item = Inner>,
+    Inner: Iterator<Item = Token>,
+{
+    let mut scores = Vec::new();
+    for (i, line) in lines.enumerate() {
+        if let LineAnalysis::Incomplete(closing) = analyze(i, line)? {
+            let score = closing.into_iter().try_fold(0usize, |acc, ty| {
+                acc.checked_mul(5)?.checked_add(match ty {
+                    Round => 1,
+                    Square => 2,
+                    Curly => 3,
+                    Angle => 4,
+                })
+            });
+            scores.push(score.ok_or_else(|| {
+                Error::at_position(
+                    Position {
+                        line: i + 1,
+                        column: 1,
+                    },
+                    ErrorKind::IntegerOverflow,
+                )
+            })?);
+        }
+    }
+    scores.sort_unstable();
+    scores
+        .get(scores.len() / 2)
+        .copied()
+        .ok_or_else(|| Error::no_solution("every line is corrupted"))
+}
+
+pub struct SyntaxScoring;
+
+impl Solution for SyntaxScoring {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 10;
+    const NAME: &'static str = "day_10_syntax_scoring";
+
+    type Input = Vec<Vec<Token>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input
+            .lines()
+            .map(|line| tokenize(line).map_err(|err| err.within(input, line)))
+            .collect()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        part_1(input.into_iter().map(Vec::into_iter))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        part_2(input.into_iter().map(Vec::into_iter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> impl Iterator<Item = impl Iterator<Item = Token>> {
+        "[({(<(())[]>[[{[]{<()<>>\n\
+        [(()[<>])]({[<{<<[]>>(\n\
+        {([(<{}[<>[]}>{[]{[(<()>\n\
+        (((({<>}<{<{<>}{[]{[]{}\n\
+        [[<[([]))<([[{}[[()]]]\n\
+        [{[{({}]{}}([{[{{{}}([]\n\
+        {<[[]]>}<{[{[{[]{()[[[]\n\
+        [<(<(<(<{}))><([]([]()\n\
+        <{([([[(<>()){}]>(<<{{\n\
+        <{([{{}}[<[[[<>{}]]]>[]]"
+            .lines()
+            .map(|line| tokenize(line).unwrap().into_iter())
+    }
+
+    fn input() -> impl Iterator<Item = impl Iterator<Item = Token>> {
+        SyntaxScoring::parse(&input!(2021, "day_10_syntax_scoring"))
+            .unwrap()
+            .into_iter()
+            .map(Vec::into_iter)
+    }
+
+    #[test]
+    fn test_part_1_sample() {
+        assert_eq!(part_1(sample()), Ok(26397));
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(input()), Ok(343863));
+    }
+
+    #[test]
+    fn test_part_2_sample() {
+        assert_eq!(part_2(sample()), Ok(288957));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(input()), Ok(2924734236));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            SyntaxScoring::parse("()\n(x)").unwrap_err().to_string(),
+            "line 2, column 2: unexpected character 'x', expected one of ()[]{}<>"
+        );
+        assert_eq!(
+            part_1(
+                SyntaxScoring::parse("()\n())")
+                    .unwrap()
+                    .into_iter()
+                    .map(Vec::into_iter)
+            )
+            .unwrap_err()
+            .to_string(),
+            "line 2, column 3: unexpected character ')', \
+             expected a closing token to have an opening token"
+        );
+        assert_eq!(
+            part_2(
+                SyntaxScoring::parse(&format!("()\n{}", "(".repeat(40)))
+                    .unwrap()
+                    .into_iter()
+                    .map(Vec::into_iter)
+            )
+            .unwrap_err()
+            .to_string(),
+            "line 2, column 1: integer out of range"
+        );
+    }
+}
@@ -0,0 +1,656 @@
+use std::{
+    fmt::Debug,
+    io::{self, Write},
+    str::FromStr,
+    thread,
+    time::Duration,
+};
+
+use itertools::Either;
+
+use crate::*;
+
+/// What happens at the edges of the grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Boundary {
+    /// Octopi on the edges have fewer neighbors.
+    Clipped,
+    /// The grid wraps around, so octopi on opposite edges are neighbors.
+    Toroidal,
+}
+
+/// Which octopi a flash gives energy to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// The 4 orthogonally adjacent octopi.
+    VonNeumann,
+    /// The 8 orthogonally and diagonally adjacent octopi.
+    Moore,
+    /// The octopi at these `(dx, dy)` offsets.
+    Custom(Vec<(isize, isize)>),
+}
+
+impl Neighborhood {
+    pub fn offsets(&self) -> &[(isize, isize)] {
+        match self {
+            Neighborhood::VonNeumann => &grid::NEIGHBORS_4,
+            Neighborhood::Moore => &grid::NEIGHBORS_8,
+            Neighborhood::Custom(offsets) => offsets,
+        }
+    }
+}
+
+/// How the octopi behave. The default is the puzzle's rules.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rules {
+    pub boundary: Boundary,
+    pub neighborhood: Neighborhood,
+    /// Octopi flash when their energy goes above this...
+    pub threshold: u8,
+    /// ...and then drop to this energy at the end of the step.
+    pub reset: u8,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            boundary: Boundary::Clipped,
+            neighborhood: Neighborhood::Moore,
+            threshold: 9,
+            reset: 0,
+        }
+    }
+}
+
+impl Rules {
+    /// The octopi that a flash at `point` gives energy to. With a toroidal
+    /// boundary on a small grid, the same octopus can come up more than once.
+    fn neighbors<'a, T>(
+        &'a self,
+        grid: &Grid<T>,
+        point: Point,
+    ) -> impl Iterator<Item = Point> + 'a {
+        let offsets = self.neighborhood.offsets();
+        match self.boundary {
+            Boundary::Clipped => Either::Left(grid.neighbors(point, offsets)),
+            Boundary::Toroidal => Either::Right(grid.wrapping_neighbors(point, offsets)),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct OctoGrid {
+    energies: Grid<u8>,
+    rules: Rules,
+}
+
+impl Debug for OctoGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.energies)
+    }
+}
+
+impl FromStr for OctoGrid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            energies: Grid::parse_digits(s)?,
+            rules: Rules::default(),
+        })
+    }
+}
+
+impl OctoGrid {
+    pub fn with_rules(self, rules: Rules) -> Self {
+        Self { rules, ..self }
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn print_diff(&self, other: &OctoGrid) {
+        for (r1, r2) in self.energies.rows().zip(other.energies.rows()) {
+            for (a, b) in r1.iter().zip(r2.iter()) {
+                print!("{:3} ", *a as i8 - *b as i8);
+            }
+            println!();
+        }
+    }
+
+    pub fn step(&mut self) -> usize {
+        self.step_traced().flashes()
+    }
+
+    /// Like [`step`](Self::step), but records which octopi flashed and when.
+    pub fn step_traced(&mut self) -> StepTrace {
+        let Self { energies, rules } = self;
+        let mut flashed = Grid::new(energies.width(), energies.height(), false);
+        let mut waves = Vec::new();
+
+        let mut first_wave = Vec::new();
+        for point in energies.points() {
+            energies[point] = energies[point].saturating_add(1);
+            if energies[point] > rules.threshold {
+                first_wave.push(point);
+                flashed[point] = true;
+            }
+        }
+        if !first_wave.is_empty() {
+            waves.push(first_wave);
+        }
+
+        // Each wave of flashes can set off the next.
+        let mut i = 0;
+        while i < waves.len() {
+            let mut next_wave = Vec::new();
+            for &to_flash in &waves[i] {
+                for neighbor in rules.neighbors(energies, to_flash) {
+                    energies[neighbor] = energies[neighbor].saturating_add(1);
+                    if !flashed[neighbor] && energies[neighbor] > rules.threshold {
+                        next_wave.push(neighbor);
+                        flashed[neighbor] = true;
+                    }
+                }
+            }
+            if !next_wave.is_empty() {
+                waves.push(next_wave);
+            }
+            i += 1;
+        }
+
+        for &point in waves.iter().flatten() {
+            energies[point] = rules.reset;
+        }
+
+        StepTrace {
+            waves,
+            energies: energies.clone(),
+        }
+    }
+
+    /// Step until every octopus flashes at once, the grid gets back to a
+    /// state it's been in before, or `max_steps` steps have passed.
+    ///
+    /// Since the octopi only depend on the last state, a repeated state means
+    /// they'll loop through the same states forever. If they haven't
+    /// synchronized by then, they never will.
+    ///
+    /// Repeats are found with Brent's algorithm, which only keeps a couple of
+    /// states around, and takes at most about twice as many steps as it takes
+    /// to get back to a state.
+    /// https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm
+    pub fn synchronize(&mut self, max_steps: usize) -> Synchronization {
+        let initial = self.clone();
+        // The grid itself is the hare, and the tortoise jumps to it whenever
+        // the hare's gone a power of two steps further.
+        let mut tortoise = self.energies.clone();
+        let mut power = 1;
+        let mut period = 0;
+        for step in 1..=max_steps {
+            if self.step_traced().is_synchronized() {
+                return Synchronization::At { step };
+            }
+            period += 1;
+            if self.energies == tortoise {
+                return Synchronization::Never {
+                    pre_period: initial.pre_period(period),
+                    period,
+                };
+            }
+            if period == power {
+                tortoise.clone_from(&self.energies);
+                power *= 2;
+                period = 0;
+            }
+        }
+        Synchronization::GaveUp { steps: max_steps }
+    }
+
+    /// How many steps it takes to get into a loop of `period` states, by
+    /// stepping two grids `period` steps apart until they're the same.
+    fn pre_period(mut self, period: usize) -> usize {
+        let mut ahead = self.clone();
+        for _ in 0..period {
+            ahead.step();
+        }
+        let mut pre_period = 0;
+        while self.energies != ahead.energies {
+            self.step();
+            ahead.step();
+            pre_period += 1;
+        }
+        pre_period
+    }
+
+    /// Run `steps` steps, recording each of them.
+    pub fn trace(&mut self, steps: usize) -> Trace {
+        Trace {
+            initial: self.energies.clone(),
+            steps: (0..steps).map(|_| self.step_traced()).collect(),
+        }
+    }
+}
+
+/// Whether and when the octopi all flash at once, from
+/// [`OctoGrid::synchronize`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Synchronization {
+    /// Every octopus flashed during this step, counting from 1.
+    At { step: usize },
+    /// The octopi never synchronize. After `pre_period` steps, they loop
+    /// through the same `period` states forever.
+    Never { pre_period: usize, period: usize },
+    /// Neither happened within this many steps.
+    GaveUp { steps: usize },
+}
+
+/// What happened during one [`OctoGrid::step`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StepTrace {
+    /// The octopi that flashed, in cascade order. The first wave was charged
+    /// by the step itself, and each later wave by the flashes of the wave
+    /// before it.
+    pub waves: Vec<Vec<Point>>,
+    /// Every octopus's energy after the step.
+    pub energies: Grid<u8>,
+}
+
+impl StepTrace {
+    pub fn flashes(&self) -> usize {
+        self.waves.iter().map(Vec::len).sum()
+    }
+
+    /// Whether every octopus flashed at once.
+    pub fn is_synchronized(&self) -> bool {
+        self.flashes() == self.energies.len()
+    }
+
+    /// The wave each octopus flashed in, counting from 0, or `None` if it
+    /// didn't flash.
+    pub fn wave_grid(&self) -> Grid<Option<usize>> {
+        let mut ret = Grid::new(self.energies.width(), self.energies.height(), None);
+        for (wave, points) in self.waves.iter().enumerate() {
+            for &point in points {
+                ret[point] = Some(wave);
+            }
+        }
+        ret
+    }
+}
+
+/// A recording of several steps, from [`OctoGrid::trace`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace {
+    /// The energies before the first step.
+    pub initial: Grid<u8>,
+    pub steps: Vec<StepTrace>,
+}
+
+impl Trace {
+    /// One text frame for the initial grid and one for each step after it.
+    ///
+    /// Each frame is a summary line, then the energies next to a map of the
+    /// wave each octopus flashed in: `1` to `9`, then `a` to `z`, then `+`
+    /// for waves after that, and `.` if it didn't flash.
+    pub fn frames(&self) -> impl Iterator<Item = String> + '_ {
+        (0..=self.steps.len()).map(|i| {
+            let (header, energies, waves) = self.frame(i);
+            let mut ret = header;
+            ret.push('\n');
+            for (energy_row, wave_row) in energies.rows().zip(waves.rows()) {
+                ret.extend(energy_row.iter().map(|&energy| energy_char(energy)));
+                ret.push_str("  ");
+                ret.extend(wave_row.iter().map(|wave| match wave {
+                    Some(wave) => char::from_digit(*wave as u32 + 1, 36).unwrap_or('+'),
+                    None => '.',
+                }));
+                ret.push('\n');
+            }
+            ret
+        })
+    }
+
+    /// Play the frames in a terminal, `delay` apart, with the octopi that
+    /// flashed highlighted.
+    pub fn animate(&self, w: &mut impl Write, delay: Duration) -> io::Result<()> {
+        const CLEAR: &str = "\x1b[2J\x1b[H";
+        const FLASH: &str = "\x1b[1;93m";
+        const DIM: &str = "\x1b[2m";
+        const RESET: &str = "\x1b[0m";
+
+        for i in 0..=self.steps.len() {
+            if i > 0 {
+                thread::sleep(delay);
+            }
+            let (header, energies, waves) = self.frame(i);
+            writeln!(w, "{}{}", CLEAR, header)?;
+            for (energy_row, wave_row) in energies.rows().zip(waves.rows()) {
+                for (&energy, wave) in energy_row.iter().zip(wave_row) {
+                    let style = if wave.is_some() { FLASH } else { DIM };
+                    write!(w, "{}{}{}", style, energy_char(energy), RESET)?;
+                }
+                writeln!(w)?;
+            }
+            w.flush()?;
+        }
+        Ok(())
+    }
+
+    /// The summary line, energies and flash waves for frame `i`, where frame
+    /// 0 is the initial grid.
+    fn frame(&self, i: usize) -> (String, &Grid<u8>, Grid<Option<usize>>) {
+        if i == 0 {
+            return (
+                "step 0".to_owned(),
+                &self.initial,
+                self.initial.map(|_| None),
+            );
+        }
+        let step = &self.steps[i - 1];
+        let mut header = format!(
+            "step {}: {} flashes in {} waves",
+            i,
+            step.flashes(),
+            step.waves.len()
+        );
+        if step.is_synchronized() {
+            header.push_str(" (synchronized)");
+        }
+        (header, &step.energies, step.wave_grid())
+    }
+}
+
+/// Energies are shown as one hex digit, or `+` if they're above `F`, which
+/// can happen with a higher [`Rules::threshold`].
+fn energy_char(energy: u8) -> char {
+    char::from_digit(energy.into(), 16).unwrap_or('+')
+}
+
+pub fn part_1(mut input: OctoGrid) -> usize {
+    (0..100).map(|_| input.step()).sum()
+}
+
+/// How many steps part 2 waits for the octopi to synchronize.
+const MAX_STEPS: usize = 1_000_000;
+
+pub fn part_2(mut input: OctoGrid) -> Result<usize, Error> {
+    match input.synchronize(MAX_STEPS) {
+        Synchronization::At { step } => Ok(step),
+        Synchronization::Never { .. } => {
+            Err(Error::no_solution("the octopi never all flash at once"))
+        }
+        Synchronization::GaveUp { .. } => Err(Error::no_solution(
+            "the octopi didn't all flash at once within a million steps",
+        )),
+    }
+}
+
+pub struct DumboOctopus;
+
+impl Solution for DumboOctopus {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 11;
+    const NAME: &'static str = "day_11_dumbo_octopus";
+
+    type Input = OctoGrid;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> OctoGrid {
+        "5483143223\n\
+         2745854711\n\
+         5264556173\n\
+         6141336146\n\
+         6357385478\n\
+         4167524645\n\
+         2176841721\n\
+         6882881134\n\
+         4846848554\n\
+         5283751526"
+            .parse()
+            .unwrap()
+    }
+
+    fn input() -> OctoGrid {
+        input!(2021, "day_11_dumbo_octopus").parse().unwrap()
+    }
+
+    #[test]
+    fn test_part_1_sample_step() {
+        let mut sample = sample();
+        sample.step();
+        assert_eq!(
+            sample,
+            "6594254334\n\
+            3856965822\n\
+            6375667284\n\
+            7252447257\n\
+            7468496589\n\
+            5278635756\n\
+            3287952832\n\
+            7993992245\n\
+            5957959665\n\
+            6394862637"
+                .parse()
+                .unwrap()
+        );
+        sample.step();
+        assert_eq!(
+            sample,
+            "8807476555\n\
+            5089087054\n\
+            8597889608\n\
+            8485769600\n\
+            8700908800\n\
+            6600088989\n\
+            6800005943\n\
+            0000007456\n\
+            9000000876\n\
+            8700006848"
+                .parse()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_step_any_size() {
+        let mut small: OctoGrid = "11111\n\
+                                   19991\n\
+                                   19191\n\
+                                   19991\n\
+                                   11111"
+            .parse()
+            .unwrap();
+        assert_eq!(small.step(), 9);
+        assert_eq!(
+            small,
+            "34543\n\
+             40004\n\
+             50005\n\
+             40004\n\
+             34543"
+                .parse()
+                .unwrap()
+        );
+
+        let mut column: OctoGrid = "9\n8\n0".parse().unwrap();
+        assert_eq!(column.step(), 2);
+        assert_eq!(column, "0\n0\n2".parse().unwrap());
+    }
+
+    #[test]
+    fn test_rules() {
+        let step = |grid: &str, rules: Rules| {
+            let mut grid = grid.parse::<OctoGrid>().unwrap().with_rules(rules);
+            let flashes = grid.step();
+            (flashes, grid.energies.to_string())
+        };
+
+        assert_eq!(step("909", Rules::default()), (2, "030\n".to_owned()));
+        let toroidal = Rules {
+            boundary: Boundary::Toroidal,
+            ..Rules::default()
+        };
+        assert_eq!(step("909", toroidal), (2, "070\n".to_owned()));
+
+        let von_neumann = Rules {
+            neighborhood: Neighborhood::VonNeumann,
+            ..Rules::default()
+        };
+        assert_eq!(
+            step("11111\n19991\n19191\n19991\n11111", von_neumann),
+            (8, "23332\n30003\n30603\n30003\n23332\n".to_owned())
+        );
+
+        let custom = Rules {
+            neighborhood: Neighborhood::Custom(vec![(2, 0)]),
+            ..Rules::default()
+        };
+        assert_eq!(step("900", custom), (1, "012\n".to_owned()));
+
+        let low_threshold = Rules {
+            threshold: 5,
+            reset: 1,
+            ..Rules::default()
+        };
+        assert_eq!(step("45", low_threshold), (2, "11\n".to_owned()));
+    }
+
+    #[test]
+    fn test_synchronize() {
+        assert_eq!(
+            sample().synchronize(1000),
+            Synchronization::At { step: 195 }
+        );
+        assert_eq!(
+            sample().synchronize(100),
+            Synchronization::GaveUp { steps: 100 }
+        );
+
+        // Octopi with no neighbors keep their own time, so these two are
+        // always 5 steps apart.
+        let rules = Rules {
+            neighborhood: Neighborhood::Custom(Vec::new()),
+            ..Rules::default()
+        };
+        let mut grid = "05".parse::<OctoGrid>().unwrap().with_rules(rules);
+        assert_eq!(
+            grid.synchronize(1000),
+            Synchronization::Never {
+                pre_period: 0,
+                period: 10
+            }
+        );
+        assert!(part_2(grid).is_err());
+
+        // Resetting to 5 means the first octopus takes 5 steps to get into
+        // the same loop of 5 as the second, which is always out of step.
+        let rules = Rules {
+            neighborhood: Neighborhood::Custom(Vec::new()),
+            reset: 5,
+            ..Rules::default()
+        };
+        let mut grid = "06".parse::<OctoGrid>().unwrap().with_rules(rules);
+        assert_eq!(
+            grid.synchronize(1000),
+            Synchronization::Never {
+                pre_period: 5,
+                period: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut small: OctoGrid = "11111\n\
+                                   19991\n\
+                                   19191\n\
+                                   19991\n\
+                                   11111"
+            .parse()
+            .unwrap();
+        let trace = small.trace(2);
+        assert_eq!(trace.steps[0].waves.len(), 2);
+        assert_eq!(trace.steps[0].waves[1], [(2, 2)]);
+        assert_eq!(trace.steps[1].flashes(), 0);
+        assert_eq!(
+            trace.frames().collect::<Vec<_>>(),
+            [
+                "step 0\n\
+                 11111  .....\n\
+                 19991  .....\n\
+                 19191  .....\n\
+                 19991  .....\n\
+                 11111  .....\n",
+                "step 1: 9 flashes in 2 waves\n\
+                 34543  .....\n\
+                 40004  .111.\n\
+                 50005  .121.\n\
+                 40004  .111.\n\
+                 34543  .....\n",
+                "step 2: 0 flashes in 0 waves\n\
+                 45654  .....\n\
+                 51115  .....\n\
+                 61116  .....\n\
+                 51115  .....\n\
+                 45654  .....\n",
+            ]
+        );
+
+        let mut out = Vec::new();
+        trace.animate(&mut out, Duration::ZERO).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("\x1b[2J").count(), 3);
+        assert!(out.contains("\x1b[1;93m0\x1b[0m"));
+    }
+
+    #[test]
+    fn test_trace_sample_synchronizes() {
+        let trace = sample().trace(195);
+        assert!(trace.steps[194].is_synchronized());
+        assert!(!trace.steps[193].is_synchronized());
+        let last = trace.frames().last().unwrap();
+        let header = last.lines().next().unwrap();
+        assert!(header.starts_with("step 195: 100 flashes in "));
+        assert!(header.ends_with(" (synchronized)"));
+    }
+
+    #[test]
+    fn test_part_1_sample() {
+        assert_eq!(part_1(sample()), 1656);
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(input()), 1705);
+    }
+
+    #[test]
+    fn test_part_2_sample() {
+        assert_eq!(part_2(sample()), Ok(195));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(input()), Ok(265));
+    }
+}
@@ -0,0 +1,864 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use bitvec::prelude::*;
+
+use crate::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Cave {
+    Start,
+    End,
+    Small(String),
+    Large(String),
+}
+
+impl Cave {
+    pub fn is_small(&self) -> bool {
+        matches!(self, Cave::Small(_))
+    }
+
+    pub fn is_large(&self) -> bool {
+        matches!(self, Cave::Large(_))
+    }
+}
+
+impl Display for Cave {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cave::Start => write!(f, "start"),
+            Cave::End => write!(f, "end"),
+            Cave::Small(name) | Cave::Large(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl FromStr for Cave {
+    type Err = ErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "start" => Ok(Cave::Start),
+            "end" => Ok(Cave::End),
+            _ if s.chars().all(|c| c.is_ascii_lowercase()) => Ok(Cave::Small(s.to_owned())),
+            _ if s.chars().all(|c| c.is_ascii_uppercase()) => Ok(Cave::Large(s.to_owned())),
+            _ => Err(ErrorKind::UnexpectedToken {
+                found: s.to_owned(),
+                expected: "\"start\", \"end\", or a name in all lowercase or all uppercase",
+            }),
+        }
+    }
+}
+
+/// The index of a cave in [`CaveSystem::caves`].
+type CaveId = usize;
+
+const START: CaveId = 0;
+const END: CaveId = 1;
+
+/// A set of small caves, with one bit for each, so there can be any number
+/// of them.
+type Visited = BitVec;
+
+pub struct CaveSystem {
+    /// Every cave, starting with start and end, then the rest in the order
+    /// they're first mentioned.
+    caves: Vec<Cave>,
+    ids: HashMap<Cave, CaveId>,
+    /// The neighbors of each cave.
+    adjacency: Vec<Vec<CaveId>>,
+    /// The bit for each cave in a [`Visited`] set, if it's small.
+    bits: Vec<Option<usize>>,
+    /// Each passage, in the order they're listed.
+    connections: Vec<(CaveId, CaveId)>,
+}
+
+impl FromStr for CaveSystem {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut system = Self {
+            caves: Vec::new(),
+            ids: HashMap::new(),
+            adjacency: Vec::new(),
+            bits: Vec::new(),
+            connections: Vec::new(),
+        };
+        system.intern(Cave::Start);
+        system.intern(Cave::End);
+
+        for line in s.lines() {
+            let (a, b) = expect_split(s, line, "-")?;
+            let mut parse_cave = |name: &str| {
+                let cave: Cave = name.parse().map_err(|kind| Error::at(s, name, kind))?;
+                Ok(system.intern(cave))
+            };
+            let (a, b) = (parse_cave(a)?, parse_cave(b)?);
+            if system.caves[a].is_large() && system.caves[b].is_large() {
+                return Err(Error::at(
+                    s,
+                    line,
+                    ErrorKind::NoSolution(
+                        "two large caves are connected, so there are infinitely many paths",
+                    ),
+                ));
+            }
+            system.adjacency[a].push(b);
+            system.adjacency[b].push(a);
+            system.connections.push((a, b));
+        }
+        Ok(system)
+    }
+}
+
+impl CaveSystem {
+    /// The id of `cave`, adding it if it's new.
+    fn intern(&mut self, cave: Cave) -> CaveId {
+        if let Some(&id) = self.ids.get(&cave) {
+            return id;
+        }
+        let id = self.caves.len();
+        let bit = cave.is_small().then(|| self.small_caves());
+        self.caves.push(cave.clone());
+        self.ids.insert(cave, id);
+        self.adjacency.push(Vec::new());
+        self.bits.push(bit);
+        id
+    }
+
+    fn small_caves(&self) -> usize {
+        self.bits.iter().flatten().count()
+    }
+
+    pub fn neighbors(&self, cave: &Cave) -> impl Iterator<Item = &Cave> {
+        self.ids
+            .get(cave)
+            .map_or(&[][..], |&id| &self.adjacency[id])
+            .iter()
+            .map(|&neighbor| &self.caves[neighbor])
+    }
+
+    /// How many paths from start to end `policy` allows. Counts are memoised
+    /// on where a path is and what it's visited so far, so this takes time in
+    /// proportion to the number of those, however many paths there are.
+    pub fn distinct_paths(&self, policy: &VisitPolicy) -> usize {
+        self.count_paths(policy, &|_, _| false)
+    }
+
+    /// How many paths from start to end `policy` allows that don't walk any
+    /// passage `avoid` is true for.
+    fn count_paths(&self, policy: &VisitPolicy, avoid: &dyn Fn(CaveId, CaveId) -> bool) -> usize {
+        let rules = Rules::new(self, policy);
+        let state = rules.start(self);
+        self.count_paths_from(&rules, START, state, avoid, &mut HashMap::new())
+    }
+
+    fn count_paths_from(
+        &self,
+        rules: &Rules,
+        cave: CaveId,
+        state: State,
+        avoid: &dyn Fn(CaveId, CaveId) -> bool,
+        memo: &mut HashMap<(CaveId, State), usize>,
+    ) -> usize {
+        if cave == END {
+            return usize::from(rules.is_finished(&state));
+        }
+        if let Some(&count) = memo.get(&(cave, state.clone())) {
+            return count;
+        }
+
+        let mut count = 0;
+        for &neighbor in &self.adjacency[cave] {
+            if avoid(cave, neighbor) {
+                continue;
+            }
+            if let Some(next) = rules.enter(self, &state, neighbor) {
+                count += self.count_paths_from(rules, neighbor, next, avoid, memo);
+            }
+        }
+        memo.insert((cave, state), count);
+        count
+    }
+
+    /// Every path [`Self::distinct_paths`] counts, one at a time, searching
+    /// depth-first so only the path so far is kept.
+    pub fn paths(&self, policy: &VisitPolicy) -> Paths<'_> {
+        let rules = Rules::new(self, policy);
+        let state = rules.start(self);
+        Paths {
+            system: self,
+            rules,
+            max_len: usize::MAX,
+            stack: vec![(START, 0, state)],
+        }
+    }
+
+    /// The cave system as a Graphviz DOT graph, with each cave and passage
+    /// labelled with how many of the paths [`Self::paths`] finds go through
+    /// it. Start and end are double circles, and large caves are boxes.
+    ///
+    /// The paths through a cave or passage are counted like
+    /// [`Self::distinct_paths`], as every path minus the ones that avoid it,
+    /// so the paths are never listed one by one.
+    pub fn to_dot(&self, policy: &VisitPolicy) -> String {
+        let total = self.distinct_paths(policy);
+        let through =
+            |avoid: &dyn Fn(CaveId, CaveId) -> bool| total - self.count_paths(policy, avoid);
+
+        let mut dot = "graph caves {\n".to_owned();
+        let all_caves = self.connections.iter().flat_map(|&(a, b)| [a, b]);
+        for id in all_caves.unique() {
+            let cave = &self.caves[id];
+            let shape = match cave {
+                Cave::Start | Cave::End => "doublecircle",
+                Cave::Small(_) => "ellipse",
+                Cave::Large(_) => "box",
+            };
+            writeln!(
+                dot,
+                "    \"{}\" [shape={}, label=\"{}\\n{}\"];",
+                cave,
+                shape,
+                cave,
+                through(&|a, b| a == id || b == id)
+            )
+            .unwrap();
+        }
+        for &(a, b) in &self.connections {
+            let passage = sorted_pair((a, b));
+            let count = through(&|from, to| sorted_pair((from, to)) == passage);
+            writeln!(
+                dot,
+                "    \"{}\" -- \"{}\" [label=\"{}\"];",
+                self.caves[a], self.caves[b], count
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// The two caves at either end of a passage, in the same order whichever way
+/// it's walked.
+fn sorted_pair((a, b): (CaveId, CaveId)) -> (CaveId, CaveId) {
+    (a.min(b), a.max(b))
+}
+
+/// Which paths through a [`CaveSystem`] count. By default, paths can visit
+/// each small cave once and large caves any number of times.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VisitPolicy {
+    /// How many times in all a path can go back into a small cave it's
+    /// already visited.
+    pub revisits: usize,
+    /// Caves that can be visited at most this many times, without using up
+    /// any of the `revisits`.
+    pub limits: Vec<(Cave, usize)>,
+    /// Caves every path has to visit.
+    pub required: Vec<Cave>,
+    /// Caves no path can visit.
+    pub forbidden: Vec<Cave>,
+}
+
+impl VisitPolicy {
+    /// Part 1's rules: no small cave more than once.
+    pub fn part_1() -> Self {
+        Self::default()
+    }
+
+    /// Part 2's rules: one small cave can be visited twice.
+    pub fn part_2() -> Self {
+        Self::default().with_revisits(1)
+    }
+
+    pub fn with_revisits(self, revisits: usize) -> Self {
+        Self { revisits, ..self }
+    }
+
+    pub fn limit(mut self, cave: Cave, visits: usize) -> Self {
+        self.limits.push((cave, visits));
+        self
+    }
+
+    pub fn require(mut self, cave: Cave) -> Self {
+        self.required.push(cave);
+        self
+    }
+
+    pub fn forbid(mut self, cave: Cave) -> Self {
+        self.forbidden.push(cave);
+        self
+    }
+}
+
+/// A [`VisitPolicy`] looked up for each cave in a [`CaveSystem`].
+struct Rules {
+    /// For each cave with a limit, its index in [`State::counts`] and the
+    /// limit.
+    limits: Vec<Option<(usize, usize)>>,
+    /// For each required cave, its index in [`State::required`].
+    required: Vec<Option<usize>>,
+    /// Whether a required cave isn't in the cave system, so no path counts.
+    missing_required: bool,
+    forbidden: Vec<bool>,
+    revisits: usize,
+}
+
+/// Where a path's been so far, as far as the [`Rules`] are concerned.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct State {
+    /// The small caves without a limit that the path's visited.
+    visited: Visited,
+    /// How many times the path's visited each cave with a limit.
+    counts: Vec<usize>,
+    /// Which required caves the path's visited.
+    required: BitVec,
+    /// How many more times it can go back into a small cave.
+    revisits: usize,
+}
+
+impl Rules {
+    fn new(system: &CaveSystem, policy: &VisitPolicy) -> Self {
+        let id = |cave: &Cave| system.ids.get(cave).copied();
+        let mut limits = vec![None; system.caves.len()];
+        let mut slots = 0;
+        for (cave, limit) in &policy.limits {
+            if let Some(id) = id(cave) {
+                let slot = limits[id].map_or_else(
+                    || {
+                        slots += 1;
+                        slots - 1
+                    },
+                    |(slot, _)| slot,
+                );
+                limits[id] = Some((slot, *limit));
+            }
+        }
+
+        let mut required = vec![None; system.caves.len()];
+        let mut missing_required = false;
+        let mut slots = 0;
+        for cave in &policy.required {
+            match id(cave) {
+                Some(id) => {
+                    if required[id].is_none() {
+                        required[id] = Some(slots);
+                        slots += 1;
+                    }
+                }
+                None => missing_required = true,
+            }
+        }
+
+        let mut forbidden = vec![false; system.caves.len()];
+        for id in policy.forbidden.iter().filter_map(id) {
+            forbidden[id] = true;
+        }
+
+        Self {
+            limits,
+            required,
+            missing_required,
+            forbidden,
+            revisits: policy.revisits,
+        }
+    }
+
+    /// Where a path through `system` is before it leaves the start.
+    fn start(&self, system: &CaveSystem) -> State {
+        let mut required = bitvec![0; self.required.iter().flatten().count()];
+        if let Some(slot) = self.required[START] {
+            required.set(slot, true);
+        }
+        State {
+            visited: BitVec::repeat(false, system.small_caves()),
+            counts: vec![0; self.limits.iter().flatten().count()],
+            required,
+            revisits: self.revisits,
+        }
+    }
+
+    /// Where a path in `state` is after going into `cave`, or `None` if it
+    /// can't.
+    fn enter(&self, system: &CaveSystem, state: &State, cave: CaveId) -> Option<State> {
+        if cave == START || self.forbidden[cave] {
+            return None;
+        }
+
+        let mut next = state.clone();
+        let bit = system.bits[cave];
+        if let Some((slot, limit)) = self.limits[cave] {
+            if next.counts[slot] == limit {
+                return None;
+            }
+            next.counts[slot] += 1;
+        } else if !bit.is_some_and(|bit| next.visited[bit]) {
+            if let Some(bit) = bit {
+                next.visited.set(bit, true);
+            }
+        } else if next.revisits > 0 {
+            next.revisits -= 1;
+        } else {
+            return None;
+        }
+        if let Some(slot) = self.required[cave] {
+            next.required.set(slot, true);
+        }
+        Some(next)
+    }
+
+    /// Whether a path in `state` counts once it gets to the end.
+    fn is_finished(&self, state: &State) -> bool {
+        !self.missing_required && state.required.all()
+    }
+}
+
+/// One way through a [`CaveSystem`] from start to end, displayed like
+/// `start,A,b,A,end`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Path<'a>(pub Vec<&'a Cave>);
+
+impl<'a> Path<'a> {
+    /// How many caves the path goes through, counting start, end and every
+    /// revisit.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn visits(&self, cave: &Cave) -> bool {
+        self.0.contains(&cave)
+    }
+
+    /// Each passage walked, in order.
+    pub fn passages(&self) -> impl Iterator<Item = (&'a Cave, &'a Cave)> + '_ {
+        self.0.iter().copied().tuple_windows()
+    }
+}
+
+impl Display for Path<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.iter().join(","))
+    }
+}
+
+/// The paths through a [`CaveSystem`], from [`CaveSystem::paths`].
+pub struct Paths<'a> {
+    system: &'a CaveSystem,
+    rules: Rules,
+    max_len: usize,
+    /// Each cave on the path so far, with the index of the next of its
+    /// neighbors to try and where the path is once it's got there.
+    stack: Vec<(CaveId, usize, State)>,
+}
+
+impl Paths<'_> {
+    /// Only find paths through at most `max_len` caves, without exploring any
+    /// longer ones.
+    pub fn max_len(self, max_len: usize) -> Self {
+        Self { max_len, ..self }
+    }
+}
+
+impl<'a> Iterator for Paths<'a> {
+    type Item = Path<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let system = self.system;
+        loop {
+            let depth = self.stack.len();
+            let (cave, next, state) = self.stack.last_mut()?;
+            let Some(&neighbor) = system.adjacency[*cave].get(*next) else {
+                // Every way on from here has been tried.
+                self.stack.pop();
+                continue;
+            };
+            *next += 1;
+
+            // Anything but the end needs room for the end after it.
+            let room = if neighbor == END { 1 } else { 2 };
+            if depth + room > self.max_len {
+                continue;
+            }
+            let Some(state) = self.rules.enter(system, state, neighbor) else {
+                continue;
+            };
+            if neighbor != END {
+                self.stack.push((neighbor, 0, state));
+            } else if self.rules.is_finished(&state) {
+                let mut path = self
+                    .stack
+                    .iter()
+                    .map(|&(cave, _, _)| &system.caves[cave])
+                    .collect_vec();
+                path.push(&system.caves[END]);
+                return Some(Path(path));
+            }
+        }
+    }
+}
+
+type Input = CaveSystem;
+
+pub fn part_1(input: Input) -> usize {
+    input.distinct_paths(&VisitPolicy::part_1())
+}
+
+pub fn part_2(input: Input) -> usize {
+    input.distinct_paths(&VisitPolicy::part_2())
+}
+
+pub struct PassagePathing;
+
+impl Solution for PassagePathing {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 12;
+    const NAME: &'static str = "day_12_passage_pathing";
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part_2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Input {
+        "fs-end\n\
+        he-DX\n\
+        fs-he\n\
+        start-DX\n\
+        pj-DX\n\
+        end-zg\n\
+        zg-sl\n\
+        zg-pj\n\
+        pj-he\n\
+        RW-he\n\
+        fs-DX\n\
+        pj-RW\n\
+        zg-RW\n\
+        start-pj\n\
+        he-WI\n\
+        zg-he\n\
+        pj-fs\n\
+        start-RW"
+            .parse()
+            .unwrap()
+    }
+
+    fn input() -> Input {
+        input!(2021, "day_12_passage_pathing").parse().unwrap()
+    }
+
+    #[test]
+    fn test_part_1_sample() {
+        assert_eq!(part_1(sample()), 226);
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(input()), 3576);
+    }
+
+    #[test]
+    fn test_part_2_sample() {
+        assert_eq!(part_2(sample()), 3509);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(input()), 84271);
+    }
+
+    #[test]
+    fn test_paths() {
+        let caves: CaveSystem = "start-A\n\
+                                 start-b\n\
+                                 A-c\n\
+                                 A-b\n\
+                                 b-d\n\
+                                 A-end\n\
+                                 b-end"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            caves
+                .paths(&VisitPolicy::part_1())
+                .map(|path| path.to_string())
+                .sorted()
+                .collect_vec(),
+            [
+                "start,A,b,A,c,A,end",
+                "start,A,b,A,end",
+                "start,A,b,end",
+                "start,A,c,A,b,A,end",
+                "start,A,c,A,b,end",
+                "start,A,c,A,end",
+                "start,A,end",
+                "start,b,A,c,A,end",
+                "start,b,A,end",
+                "start,b,end",
+            ]
+        );
+        assert_eq!(caves.paths(&VisitPolicy::part_2()).count(), 36);
+
+        // Filtering.
+        assert_eq!(
+            caves
+                .paths(&VisitPolicy::part_1())
+                .max_len(4)
+                .map(|path| path.to_string())
+                .collect_vec(),
+            [
+                "start,A,b,end",
+                "start,A,end",
+                "start,b,A,end",
+                "start,b,end"
+            ]
+        );
+        let c = "c".parse().unwrap();
+        assert_eq!(
+            caves
+                .paths(&VisitPolicy::part_1())
+                .filter(|path| path.visits(&c))
+                .count(),
+            5
+        );
+        assert!(caves
+            .paths(&VisitPolicy::part_2())
+            .all(|path| path.len() > 2 && !path.is_empty()));
+
+        for policy in [VisitPolicy::part_1(), VisitPolicy::part_2()] {
+            let sample = sample();
+            let paths = sample.paths(&policy).collect_vec();
+            assert_eq!(paths.len(), sample.distinct_paths(&policy));
+            assert_eq!(paths.iter().unique().count(), paths.len());
+        }
+    }
+
+    #[test]
+    fn test_visit_policy() {
+        let sample = sample();
+        let cave = |name: &str| name.parse::<Cave>().unwrap();
+        let visits = |path: &Path, name| path.0.iter().filter(|&&c| *c == cave(name)).count();
+        let check = |policy: VisitPolicy, expected: usize| {
+            assert_eq!(sample.distinct_paths(&policy), expected, "{:?}", policy);
+            assert_eq!(sample.paths(&policy).count(), expected, "{:?}", policy);
+        };
+
+        // Each rule picks out some of the paths a looser policy allows.
+        let part_1 = sample.paths(&VisitPolicy::part_1()).collect_vec();
+        let part_2 = sample.paths(&VisitPolicy::part_2()).collect_vec();
+        check(
+            VisitPolicy::part_2().require(cave("zg")),
+            part_2
+                .iter()
+                .filter(|path| path.visits(&cave("zg")))
+                .count(),
+        );
+        check(
+            VisitPolicy::part_1()
+                .require(cave("pj"))
+                .require(cave("RW")),
+            part_1
+                .iter()
+                .filter(|path| path.visits(&cave("pj")) && path.visits(&cave("RW")))
+                .count(),
+        );
+        check(
+            VisitPolicy::part_2().forbid(cave("he")).forbid(cave("DX")),
+            part_2
+                .iter()
+                .filter(|path| !path.visits(&cave("he")) && !path.visits(&cave("DX")))
+                .count(),
+        );
+        check(
+            VisitPolicy::part_1().limit(cave("pj"), 2),
+            part_2
+                .iter()
+                .filter(|path| {
+                    path.0
+                        .iter()
+                        .filter(|cave| cave.is_small())
+                        .counts()
+                        .into_iter()
+                        .all(|(c, n)| n == 1 || **c == cave("pj"))
+                })
+                .count(),
+        );
+        check(
+            VisitPolicy::part_1().limit(cave("DX"), 1),
+            part_1.iter().filter(|path| visits(path, "DX") <= 1).count(),
+        );
+        check(VisitPolicy::part_1().require(cave("nowhere")), 0);
+        check(VisitPolicy::part_1().forbid(cave("end")), 0);
+
+        let revisits = |path: &Path| {
+            path.0
+                .iter()
+                .filter(|cave| cave.is_small())
+                .counts()
+                .values()
+                .map(|n| n - 1)
+                .sum::<usize>()
+        };
+        let part_3 = sample
+            .paths(&VisitPolicy::default().with_revisits(2))
+            .collect_vec();
+        assert_eq!(
+            sample.distinct_paths(&VisitPolicy::default().with_revisits(2)),
+            part_3.len()
+        );
+        assert!(part_3.len() > part_2.len());
+        assert!(part_3.iter().all(|path| revisits(path) <= 2));
+        assert_eq!(
+            part_3.iter().filter(|path| revisits(path) <= 1).count(),
+            part_2.len()
+        );
+    }
+
+    #[test]
+    fn test_many_required_caves() {
+        // One path through a chain of large and small caves, all required.
+        let name = |i: usize| {
+            [b'a' + (i / 26) as u8, b'a' + (i % 26) as u8]
+                .into_iter()
+                .map(char::from)
+                .collect::<String>()
+        };
+        let chain = (0..35)
+            .flat_map(|i| [name(i).to_uppercase(), name(i)])
+            .collect_vec();
+        let caves: CaveSystem = ["start".to_owned()]
+            .iter()
+            .chain(&chain)
+            .chain(&["end".to_owned()])
+            .tuple_windows()
+            .map(|(a, b)| format!("{}-{}\n", a, b))
+            .collect::<String>()
+            .parse()
+            .unwrap();
+        let policy = chain.iter().fold(VisitPolicy::part_1(), |policy, name| {
+            policy.require(name.parse().unwrap())
+        });
+        assert_eq!(policy.required.len(), 70);
+        assert_eq!(caves.distinct_paths(&policy), 1);
+        assert_eq!(caves.paths(&policy).count(), 1);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let caves: CaveSystem = "start-A\n\
+                                 start-b\n\
+                                 A-c\n\
+                                 A-b\n\
+                                 b-d\n\
+                                 A-end\n\
+                                 b-end"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            caves.to_dot(&VisitPolicy::part_1()),
+            "graph caves {\n    \
+                 \"start\" [shape=doublecircle, label=\"start\\n10\"];\n    \
+                 \"A\" [shape=box, label=\"A\\n9\"];\n    \
+                 \"b\" [shape=ellipse, label=\"b\\n8\"];\n    \
+                 \"c\" [shape=ellipse, label=\"c\\n5\"];\n    \
+                 \"d\" [shape=ellipse, label=\"d\\n0\"];\n    \
+                 \"end\" [shape=doublecircle, label=\"end\\n10\"];\n    \
+                 \"start\" -- \"A\" [label=\"7\"];\n    \
+                 \"start\" -- \"b\" [label=\"3\"];\n    \
+                 \"A\" -- \"c\" [label=\"5\"];\n    \
+                 \"A\" -- \"b\" [label=\"7\"];\n    \
+                 \"b\" -- \"d\" [label=\"0\"];\n    \
+                 \"A\" -- \"end\" [label=\"7\"];\n    \
+                 \"b\" -- \"end\" [label=\"3\"];\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_many_paths() {
+        // Every order of any number of the small caves, with A in between.
+        let small_caves = |n: u8| (0..n).map(|i| format!("A-{}\n", char::from(b'a' + i)));
+        let caves: CaveSystem = ["start-A\nA-end\n".to_owned()]
+            .into_iter()
+            .chain(small_caves(12))
+            .collect::<String>()
+            .parse()
+            .unwrap();
+        assert_eq!(caves.distinct_paths(&VisitPolicy::part_1()), 1302061345);
+
+        let caves: CaveSystem = ["start-A\nA-end\n".to_owned()]
+            .into_iter()
+            .chain(small_caves(5))
+            .collect::<String>()
+            .parse()
+            .unwrap();
+        assert_eq!(caves.distinct_paths(&VisitPolicy::part_1()), 326);
+        assert_eq!(
+            caves.distinct_paths(&VisitPolicy::part_2()),
+            caves.paths(&VisitPolicy::part_2()).count()
+        );
+    }
+
+    #[test]
+    fn test_many_small_caves() {
+        // A loop through A and 65 small caves, more than fit in a u64.
+        let small_caves = (0..65u8)
+            .map(|i| format!("{}{}", char::from(b'a' + i / 26), char::from(b'a' + i % 26)));
+        let caves: CaveSystem = ["start-A\nA-end\n".to_owned()]
+            .into_iter()
+            .chain(
+                ["A".to_owned()]
+                    .into_iter()
+                    .chain(small_caves)
+                    .chain(["A".to_owned()])
+                    .tuple_windows()
+                    .map(|(a, b)| format!("{}-{}\n", a, b)),
+            )
+            .collect::<String>()
+            .parse()
+            .unwrap();
+        assert_eq!(caves.distinct_paths(&VisitPolicy::part_1()), 7);
+        assert_eq!(
+            caves.distinct_paths(&VisitPolicy::part_2()),
+            caves.paths(&VisitPolicy::part_2()).count()
+        );
+    }
+
+    #[test]
+    fn test_connected_large_caves() {
+        assert_eq!(
+            "start-A\nA-B\nB-end"
+                .parse::<CaveSystem>()
+                .err()
+                .map(|err| err.to_string()),
+            Some(
+                "line 2, column 1: no solution: \
+                 two large caves are connected, so there are infinitely many paths"
+                    .to_owned()
+            )
+        );
+    }
+}
@@ -8,18 +8,13 @@ pub struct Origami {
     folds: Vec<Fold>,
 }
 
-fn parse_coords(line: &str) -> Result<(usize, usize), String> {
-    let (x, y) = line
-        .split_once(',')
-        .ok_or_else(|| format!("Expected coordinates delimited by ',', got: {:?}", line))?;
-    Ok((
-        x.parse().map_err(|e| format!("{}: {:?}", e, line))?,
-        y.parse().map_err(|e| format!("{}: {:?}", e, line))?,
-    ))
+fn parse_coords(source: &str, line: &str) -> Result<(usize, usize), Error> {
+    let (x, y) = expect_split(source, line, ",")?;
+    Ok((parse_int(source, x)?, parse_int(source, y)?))
 }
 
 impl FromStr for Origami {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut parsing_coords = true;
@@ -32,10 +27,10 @@ impl FromStr for Origami {
                 if line.is_empty() {
                     parsing_coords = false;
                 } else {
-                    coords.insert(parse_coords(line)?);
+                    coords.insert(parse_coords(s, line)?);
                 }
             } else {
-                folds.push(line.parse()?);
+                folds.push(parse_at(s, line)?);
             }
         }
 
@@ -49,8 +44,14 @@ impl FromStr for Origami {
 pub struct Paper(HashSet<(usize, usize)>);
 
 impl Paper {
-    pub fn fold(&self, fold: Fold) -> Paper {
+    /// Fold the paper, failing if a dot would land past the paper's edge.
+    pub fn fold(&self, fold: Fold) -> Result<Paper, Error> {
         let mut ret = HashSet::with_capacity(self.0.len());
+        let reflect = |fold_at: usize, coord: usize| {
+            (2 * fold_at)
+                .checked_sub(coord)
+                .ok_or_else(|| Error::no_solution("a dot folds past the edge of the paper"))
+        };
 
         for (x, y) in self.0.iter() {
             match &fold {
@@ -58,20 +59,20 @@ impl Paper {
                     if x < fold_at {
                         ret.insert((*x, *y));
                     } else {
-                        ret.insert((2 * fold_at - *x, *y));
+                        ret.insert((reflect(*fold_at, *x)?, *y));
                     }
                 }
                 Fold::Y(fold_at) => {
                     if y < fold_at {
                         ret.insert((*x, *y));
                     } else {
-                        ret.insert((*x, 2 * fold_at - *y));
+                        ret.insert((*x, reflect(*fold_at, *y)?));
                     }
                 }
             }
         }
 
-        Paper(ret)
+        Ok(Paper(ret))
     }
 }
 
@@ -82,42 +83,43 @@ pub enum Fold {
 }
 
 impl FromStr for Fold {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        const PREFIX: &str = "fold along ";
-
-        if !s.starts_with(PREFIX) {
-            return Err(format!("Fold must start with 'fold along', got: {:?}", s));
-        }
-
-        let s = &s[PREFIX.len()..];
-
-        let (axis, coord) = s
-            .split_once('=')
-            .ok_or_else(|| format!("Expected fold to be delimited with '=', got: {:?}", s))?;
-
-        let coord: usize = coord.parse().map_err(|e| format!("{}: {:?}", e, coord))?;
+        let fold = expect_prefix(s, s, "fold along ")?;
+        let (axis, coord) = expect_split(s, fold, "=")?;
+        let coord = parse_int(s, coord)?;
 
         match axis {
             "x" => Ok(Fold::X(coord)),
             "y" => Ok(Fold::Y(coord)),
-            _ => Err(format!("Axis must be 'x' or 'y', got {:?}", axis)),
+            _ => Err(Error::at(
+                s,
+                axis,
+                ErrorKind::UnexpectedToken {
+                    found: axis.to_owned(),
+                    expected: "\"x\" or \"y\"",
+                },
+            )),
         }
     }
 }
 
 type Input = Origami;
 
-pub fn part_1(input: Input) -> usize {
-    input.paper.fold(input.folds[0]).0.len()
+pub fn part_1(input: Input) -> Result<usize, Error> {
+    let fold = input
+        .folds
+        .first()
+        .ok_or_else(|| Error::no_solution("there are no folds"))?;
+    Ok(input.paper.fold(*fold)?.0.len())
 }
 
-pub fn part_2(input: Input) -> String {
+pub fn part_2(input: Input) -> Result<String, Error> {
     let final_paper = input
         .folds
         .into_iter()
-        .fold(input.paper, |paper, fold| paper.fold(fold));
+        .try_fold(input.paper, |paper, fold| paper.fold(fold))?;
     let (x_extent, y_extent) = final_paper
         .0
         .iter()
@@ -139,7 +141,31 @@ pub fn part_2(input: Input) -> String {
         ret.push('\n');
     }
 
-    ret
+    Ok(ret)
+}
+
+pub struct TransparentOrigami;
+
+impl Solution for TransparentOrigami {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 13;
+    const NAME: &'static str = "day_13_transparent_origami";
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
@@ -173,17 +199,17 @@ mod tests {
     }
 
     fn input() -> Input {
-        input!("day_13_transparent_origami").parse().unwrap()
+        input!(2021, "day_13_transparent_origami").parse().unwrap()
     }
 
     #[test]
     fn test_part_1_sample() {
-        assert_eq!(part_1(sample()), 17);
+        assert_eq!(part_1(sample()), Ok(17));
     }
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(input()), 618);
+        assert_eq!(part_1(input()), Ok(618));
     }
 
     #[test]
@@ -191,12 +217,22 @@ mod tests {
         assert_eq!(
             part_2(input()),
             // "ALREKFKU"
-            ".##..#....###..####.#..#.####.#..#.#..#\n\
+            Ok(".##..#....###..####.#..#.####.#..#.#..#\n\
              #..#.#....#..#.#....#.#..#....#.#..#..#\n\
              #..#.#....#..#.###..##...###..##...#..#\n\
              ####.#....###..#....#.#..#....#.#..#..#\n\
              #..#.#....#.#..#....#.#..#....#.#..#..#\n\
              #..#.####.#..#.####.#..#.#....#..#..##.\n"
+                .to_owned())
+        );
+    }
+
+    #[test]
+    fn test_fold_past_edge() {
+        let origami: Origami = "1,0\n5,0\n\nfold along x=2\n".parse().unwrap();
+        assert_eq!(
+            part_1(origami),
+            Err(Error::no_solution("a dot folds past the edge of the paper"))
         );
     }
 }
@@ -22,49 +22,57 @@ fn parse_pairs(pairs: &str) -> HashMap<(char, char), usize> {
 }
 
 impl FromStr for Polymer {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
-        let pairs = lines.next().ok_or_else(|| format!("Expected first line"))?;
-        let first = pairs
-            .chars()
-            .next()
-            .ok_or_else(|| format!("Expected template to have a first and last char"))?;
-        let last = pairs
-            .chars()
-            .last()
-            .ok_or_else(|| format!("Expected template to have a first and last char"))?;
+        let pairs = lines.next().unwrap_or(s);
+        let (first, last) = match (pairs.chars().next(), pairs.chars().last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => {
+                return Err(Error::at(
+                    s,
+                    pairs,
+                    ErrorKind::UnexpectedEnd {
+                        expected: "a polymer template",
+                    },
+                ))
+            }
+        };
         let pairs = parse_pairs(pairs);
 
         lines.next(); // Discard empty line.
 
-        fn parse_rule(rule: &str) -> Result<((char, char), char), String> {
-            let (pair, insert) = rule.split_once(" -> ").ok_or_else(|| {
-                format!("Expected rule to be delimited by ' -> ', got: {:?}", rule)
+        let parse_rule = |rule| {
+            let (pair, insert) = expect_split(s, rule, " -> ")?;
+            let pair = pair.chars().collect_tuple().ok_or_else(|| {
+                Error::at(
+                    s,
+                    pair,
+                    ErrorKind::UnexpectedToken {
+                        found: pair.to_owned(),
+                        expected: "a pair of elements",
+                    },
+                )
             })?;
-            let mut pair_chars = pair.chars();
-            Ok((
-                (
-                    pair_chars
-                        .next()
-                        .ok_or_else(|| format!("Expected pair to have two chars: {:?}", pair))?,
-                    pair_chars
-                        .next()
-                        .ok_or_else(|| format!("Expected pair to have two chars: {:?}", pair))?,
-                ),
-                insert
-                    .chars()
-                    .next()
-                    .ok_or_else(|| format!("Expected insert to have char: {:?}", insert))?,
-            ))
-        }
+            let (insert,) = insert.chars().collect_tuple().ok_or_else(|| {
+                Error::at(
+                    s,
+                    insert,
+                    ErrorKind::UnexpectedToken {
+                        found: insert.to_owned(),
+                        expected: "a single element",
+                    },
+                )
+            })?;
+            Ok((pair, insert))
+        };
 
         Ok(Self {
             first,
             last,
             pairs,
-            rules: lines.map(parse_rule).collect::<Result<_, _>>()?,
+            rules: lines.map(parse_rule).collect::<Result<_, Error>>()?,
         })
     }
 }
@@ -74,7 +82,7 @@ impl Polymer {
         let mut new_pairs = HashMap::with_capacity(self.pairs.len());
 
         for (pair, count) in self.pairs.iter() {
-            if let Some(between) = self.rules.get(&pair) {
+            if let Some(between) = self.rules.get(pair) {
                 let pair_0 = new_pairs.entry((pair.0, *between)).or_default();
                 *pair_0 += *count;
                 let pair_1 = new_pairs.entry((*between, pair.1)).or_default();
@@ -111,7 +119,8 @@ impl Polymer {
     pub fn least_most_common_diff(&self) -> usize {
         match self.counts().into_values().minmax() {
             itertools::MinMaxResult::MinMax(min, max) => max - min,
-            _ => unreachable!(),
+            // Only one element, so it's both the most and least common.
+            _ => 0,
         }
     }
 }
@@ -132,6 +141,30 @@ pub fn part_2(mut input: Input) -> usize {
     input.least_most_common_diff()
 }
 
+pub struct ExtendedPolymerization;
+
+impl Solution for ExtendedPolymerization {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 14;
+    const NAME: &'static str = "day_14_extended_polymerization";
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part_2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,7 +193,9 @@ mod tests {
     }
 
     fn input() -> Input {
-        input!("day_14_extended_polymerization").parse().unwrap()
+        input!(2021, "day_14_extended_polymerization")
+            .parse()
+            .unwrap()
     }
 
     #[test]
@@ -0,0 +1,1179 @@
+use std::{
+    collections::{BinaryHeap, HashMap},
+    str::FromStr,
+};
+
+use itertools::Either;
+
+use crate::*;
+
+pub struct RiskMap(Grid<u8>);
+
+impl FromStr for RiskMap {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_digits(s).map(Self)
+    }
+}
+
+impl RiskMap {
+    /// The map tiled `factor` times in each direction, where each tile's
+    /// risks are one more than the tile above or to the left of it, wrapping
+    /// from 9 back to 1. Risks are worked out when they're needed, so this
+    /// doesn't allocate anything.
+    ///
+    /// # Panics
+    /// If `factor` is 0.
+    pub fn tiled(&self, factor: usize) -> Tiled<'_> {
+        assert!(factor > 0, "Tiled maps can't be empty");
+        Tiled { map: self, factor }
+    }
+
+    /// The full map for part 2, with every risk stored.
+    pub fn expanded(&self) -> Self {
+        let tiled = self.tiled(FACTOR);
+        Self(Grid::from_fn(tiled.width(), tiled.height(), |point| {
+            tiled.risk(point)
+        }))
+    }
+}
+
+/// How many times the map is tiled in each direction in part 2.
+const FACTOR: usize = 5;
+
+/// A view of a [`RiskMap`] tiled in both directions, from [`RiskMap::tiled`].
+#[derive(Clone, Copy)]
+pub struct Tiled<'a> {
+    map: &'a RiskMap,
+    factor: usize,
+}
+
+/// Something with a risk at each point in a rectangle, that paths can be
+/// found through.
+pub trait Risks {
+    fn width(&self) -> usize;
+
+    fn height(&self) -> usize;
+
+    /// The risk of entering `point`, from 1 to 9.
+    ///
+    /// # Panics
+    /// If `point` is out of bounds.
+    fn risk(&self, point: Point) -> u8;
+
+    /// The lowest risk anywhere.
+    fn min_risk(&self) -> u8 {
+        (0..self.height())
+            .flat_map(|y| (0..self.width()).map(move |x| (x, y)))
+            .map(|point| self.risk(point))
+            .min()
+            .unwrap_or_default()
+    }
+
+    fn contains(&self, (x, y): Point) -> bool {
+        x < self.width() && y < self.height()
+    }
+
+    /// The lowest total risk from the top left to the bottom right.
+    fn shortest_path(&self) -> usize {
+        self.find_path((0, 0), (self.width() - 1, self.height() - 1))
+            .expect("the corners are in the map")
+            .cost
+    }
+
+    /// The path with the lowest total risk from `start` to `goal`, or `None`
+    /// if either is outside the map. Every point in the map can reach every
+    /// other, so there's always a path between points inside it.
+    fn find_path(&self, start: Point, goal: Point) -> Option<Path> {
+        self.find_path_with(start, goal, Search::default())
+    }
+
+    /// Like [`find_path`](Self::find_path), but with a choice of search.
+    /// Every search finds a path with the same cost, though not always the
+    /// same path when there's a tie.
+    ///
+    /// Searches only keep track of the points they've reached, so searching
+    /// a tiled map doesn't allocate anything as big as the map unless most
+    /// of it has to be searched.
+    fn find_path_with(&self, start: Point, goal: Point, search: Search) -> Option<Path> {
+        self.find_path_by(start, goal, &Moves::PUZZLE, search)
+    }
+
+    /// Like [`find_path_with`](Self::find_path_with), but with a choice of
+    /// which moves can be made and what they cost. This is also `None` if
+    /// `goal` can't be reached from `start` with those moves, like with
+    /// knight moves on a very narrow map.
+    fn find_path_by<M: Movement, C: Cost>(
+        &self,
+        start: Point,
+        goal: Point,
+        moves: &Moves<M, C>,
+        search: Search,
+    ) -> Option<Path> {
+        if !self.contains(start) || !self.contains(goal) {
+            return None;
+        }
+        let graph = Graph::new(self, moves);
+        let heuristic = graph.heuristic(goal);
+        match (search.algorithm, search.queue) {
+            (Algorithm::Dijkstra, QueueKind::BinaryHeap) => {
+                graph.best_first::<BinaryHeap<State>>(start, goal, |_| 0)
+            }
+            (Algorithm::Dijkstra, QueueKind::Buckets) => {
+                graph.best_first::<BucketQueue>(start, goal, |_| 0)
+            }
+            (Algorithm::AStar, QueueKind::BinaryHeap) => {
+                graph.best_first::<BinaryHeap<State>>(start, goal, heuristic)
+            }
+            (Algorithm::AStar, QueueKind::Buckets) => {
+                graph.best_first::<BucketQueue>(start, goal, heuristic)
+            }
+            (Algorithm::Bidirectional, QueueKind::BinaryHeap) => {
+                graph.bidirectional::<BinaryHeap<State>>(start, goal)
+            }
+            (Algorithm::Bidirectional, QueueKind::Buckets) => {
+                graph.bidirectional::<BucketQueue>(start, goal)
+            }
+        }
+    }
+
+    /// Draw the map with `path` highlighted.
+    fn render_path(&self, path: &Path, highlight: Highlight) -> String {
+        let mut on_path = Grid::new(self.width(), self.height(), false);
+        for &point in &path.points {
+            on_path[point] = true;
+        }
+
+        let mut ret = String::with_capacity(on_path.len() * 2);
+        for (point, &on_path) in on_path.enumerate() {
+            let digit = char::from(b'0' + self.risk(point));
+            match (highlight, on_path) {
+                (Highlight::Dots, true) => ret.push(digit),
+                (Highlight::Dots, false) => ret.push('.'),
+                (Highlight::Ansi, true) => {
+                    ret.push_str("\x1b[1;97m");
+                    ret.push(digit);
+                    ret.push_str("\x1b[0m");
+                }
+                (Highlight::Ansi, false) => {
+                    ret.push_str("\x1b[2m");
+                    ret.push(digit);
+                    ret.push_str("\x1b[0m");
+                }
+            }
+            if point.0 == self.width() - 1 {
+                ret.push('\n');
+            }
+        }
+        ret
+    }
+}
+
+impl Risks for RiskMap {
+    fn width(&self) -> usize {
+        self.0.width()
+    }
+
+    fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    fn risk(&self, point: Point) -> u8 {
+        self.0[point]
+    }
+
+    fn min_risk(&self) -> u8 {
+        self.0.iter().copied().min().unwrap_or_default()
+    }
+}
+
+impl Risks for Tiled<'_> {
+    fn width(&self) -> usize {
+        self.map.width() * self.factor
+    }
+
+    fn height(&self) -> usize {
+        self.map.height() * self.factor
+    }
+
+    fn risk(&self, (x, y): Point) -> u8 {
+        assert!(self.contains((x, y)), "{:?} is out of bounds", (x, y));
+        let (width, height) = (self.map.width(), self.map.height());
+        let tiles = x / width + y / height;
+        wrap_risk(self.map.0[(x % width, y % height)] as usize + tiles)
+    }
+
+    fn min_risk(&self) -> u8 {
+        // Tiles add 0 up to 2 * (factor - 1), and adding more than 8 wraps
+        // around to a risk that's already been seen.
+        let max_added = (2 * self.factor.saturating_sub(1)).min(8);
+        self.map
+            .0
+            .iter()
+            .flat_map(|&risk| (0..=max_added).map(move |added| wrap_risk(risk as usize + added)))
+            .min()
+            .unwrap_or_default()
+    }
+}
+
+/// Wrap a risk above 9 back around to 1.
+fn wrap_risk(risk: usize) -> u8 {
+    ((risk - 1) % 9 + 1) as u8
+}
+
+/// Which moves can be made from each point. [`FourWay`], [`EightWay`] and
+/// [`Knight`] are built in, and a `Vec` of offsets can be any other moves.
+pub trait Movement {
+    /// Each move, as an offset from the point it's made from.
+    fn offsets(&self) -> &[(isize, isize)];
+
+    /// A lower bound on the number of moves it takes to go `dx` across and
+    /// `dy` down, which A* uses to aim for the goal. The default, one move
+    /// unless there's nowhere to go, is right for any moves.
+    fn min_moves(&self, dx: usize, dy: usize) -> usize {
+        usize::from(dx > 0 || dy > 0)
+    }
+}
+
+/// Up, down, left or right.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FourWay;
+
+/// Like [`FourWay`], or diagonally.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EightWay;
+
+/// Like a knight in chess: two points one way and one point the other.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Knight;
+
+impl Movement for FourWay {
+    fn offsets(&self) -> &[(isize, isize)] {
+        &grid::NEIGHBORS_4
+    }
+
+    fn min_moves(&self, dx: usize, dy: usize) -> usize {
+        dx.saturating_add(dy)
+    }
+}
+
+impl Movement for EightWay {
+    fn offsets(&self) -> &[(isize, isize)] {
+        &grid::NEIGHBORS_8
+    }
+
+    fn min_moves(&self, dx: usize, dy: usize) -> usize {
+        dx.max(dy)
+    }
+}
+
+impl Movement for Knight {
+    fn offsets(&self) -> &[(isize, isize)] {
+        &[
+            (-1, -2),
+            (1, -2),
+            (-2, -1),
+            (2, -1),
+            (-2, 1),
+            (2, 1),
+            (-1, 2),
+            (1, 2),
+        ]
+    }
+
+    fn min_moves(&self, dx: usize, dy: usize) -> usize {
+        // Each move goes 3 points in total, and at most 2 in either
+        // direction.
+        (dx.saturating_add(dy).div_ceil(3)).max(dx.max(dy).div_ceil(2))
+    }
+}
+
+impl Movement for Vec<(isize, isize)> {
+    fn offsets(&self) -> &[(isize, isize)] {
+        self
+    }
+}
+
+/// What a move costs. [`Entering`], [`Average`] and [`TurnPenalty`] are
+/// built in, and a closure from the risks of the points moved from and to
+/// can be any other cost that doesn't depend on the move before.
+///
+/// Moves and paths that would cost more than `usize::MAX` can't be made.
+pub trait Cost {
+    /// The cost of moving from a point with risk `from` to one with risk
+    /// `to`.
+    fn step(&self, from: u8, to: u8) -> usize;
+
+    /// How much more a move by `to` costs right after a move by `from`.
+    /// This is only asked for if [`has_turns`](Self::has_turns) is true.
+    fn turn(&self, _from: (isize, isize), _to: (isize, isize)) -> usize {
+        0
+    }
+
+    /// Whether [`turn`](Self::turn) can ever be more than 0. Searches then
+    /// have to keep track of which way each path is heading, which takes
+    /// longer.
+    fn has_turns(&self) -> bool {
+        false
+    }
+
+    /// A lower bound on a move's cost when no risk is below `min_risk`,
+    /// which A* uses to aim for the goal. The default, 0, is right for any
+    /// cost, but makes A* no quicker than Dijkstra's algorithm.
+    fn min_step(&self, _min_risk: u8) -> usize {
+        0
+    }
+}
+
+/// The risk of the point moved to, as in the puzzle.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Entering;
+
+/// The average risk of the points moved from and to, doubled so that it's
+/// always a whole number. That is, it's their sum, and so every path costs
+/// twice its average.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Average;
+
+/// The risk of the point moved to, plus this much if the move goes in a
+/// different direction from the move before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TurnPenalty(pub usize);
+
+impl Cost for Entering {
+    fn step(&self, _from: u8, to: u8) -> usize {
+        to.into()
+    }
+
+    fn min_step(&self, min_risk: u8) -> usize {
+        min_risk.into()
+    }
+}
+
+impl Cost for Average {
+    fn step(&self, from: u8, to: u8) -> usize {
+        usize::from(from) + usize::from(to)
+    }
+
+    fn min_step(&self, min_risk: u8) -> usize {
+        2 * usize::from(min_risk)
+    }
+}
+
+impl Cost for TurnPenalty {
+    fn step(&self, _from: u8, to: u8) -> usize {
+        to.into()
+    }
+
+    fn turn(&self, from: (isize, isize), to: (isize, isize)) -> usize {
+        if from == to {
+            0
+        } else {
+            self.0
+        }
+    }
+
+    fn has_turns(&self) -> bool {
+        true
+    }
+
+    fn min_step(&self, min_risk: u8) -> usize {
+        min_risk.into()
+    }
+}
+
+impl<F: Fn(u8, u8) -> usize> Cost for F {
+    fn step(&self, from: u8, to: u8) -> usize {
+        self(from, to)
+    }
+}
+
+/// The moves a path can make, and what they cost.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Moves<M = FourWay, C = Entering> {
+    pub movement: M,
+    pub cost: C,
+}
+
+impl Moves {
+    /// The puzzle's moves.
+    pub const PUZZLE: Self = Self {
+        movement: FourWay,
+        cost: Entering,
+    };
+}
+
+impl<M: Movement, C: Cost> Moves<M, C> {
+    /// The total cost of following `points`, or `None` if any step along
+    /// them isn't a move that can be made.
+    pub fn cost_of<R: Risks + ?Sized>(&self, risks: &R, points: &[Point]) -> Option<usize> {
+        let graph = Graph::new(risks, self);
+        let mut node = (*points.first()?, 0);
+        let mut total: usize = 0;
+        for &point in &points[1..] {
+            let (next, cost) = graph.successors(node).find(|(next, _)| next.0 == point)?;
+            total = total.checked_add(cost)?;
+            node = next;
+        }
+        Some(total)
+    }
+}
+
+/// A point, and which way the path was heading when it got there. Heading
+/// `i + 1` means that the last move was the movement's `i`th offset, and 0
+/// means there wasn't a move, or that the cost doesn't depend on it.
+///
+/// Searching backwards from the goal, the heading is the direction of the
+/// move that leaves the point instead.
+type Node = (Point, usize);
+
+/// The moves between nodes on a map.
+struct Graph<'a, R: ?Sized, M, C> {
+    risks: &'a R,
+    moves: &'a Moves<M, C>,
+    width: usize,
+    height: usize,
+    /// How many headings each point has, including heading 0.
+    headings: usize,
+}
+
+impl<'a, R: Risks + ?Sized, M: Movement, C: Cost> Graph<'a, R, M, C> {
+    fn new(risks: &'a R, moves: &'a Moves<M, C>) -> Self {
+        Self {
+            risks,
+            moves,
+            width: risks.width(),
+            height: risks.height(),
+            headings: if moves.cost.has_turns() {
+                moves.movement.offsets().len() + 1
+            } else {
+                1
+            },
+        }
+    }
+
+    /// The heading after moving by the `i`th offset.
+    fn heading(&self, i: usize) -> usize {
+        if self.headings > 1 {
+            i + 1
+        } else {
+            0
+        }
+    }
+
+    fn offset(&self, (x, y): Point, (dx, dy): (isize, isize)) -> Option<Point> {
+        let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    /// The cost of a move from `from` to `to` between headings `from_heading`
+    /// and `to_heading`, or `None` if it's too much to count.
+    fn cost(
+        &self,
+        from: Point,
+        to: Point,
+        from_heading: usize,
+        to_heading: usize,
+    ) -> Option<usize> {
+        let step = self
+            .moves
+            .cost
+            .step(self.risks.risk(from), self.risks.risk(to));
+        step.checked_add(self.turn_cost(from_heading, to_heading))
+    }
+
+    /// The cost of turning from heading `from` to heading `to`.
+    fn turn_cost(&self, from: usize, to: usize) -> usize {
+        if from == 0 || to == 0 {
+            return 0;
+        }
+        let offsets = self.moves.movement.offsets();
+        self.moves.cost.turn(offsets[from - 1], offsets[to - 1])
+    }
+
+    /// The nodes that `node` can move to, and what each move costs.
+    fn successors(&self, (point, heading): Node) -> impl Iterator<Item = (Node, usize)> + '_ {
+        let offsets = self.moves.movement.offsets();
+        offsets.iter().enumerate().filter_map(move |(i, &delta)| {
+            let next = self.offset(point, delta)?;
+            let next_heading = self.heading(i);
+            let cost = self.cost(point, next, heading, next_heading)?;
+            Some(((next, next_heading), cost))
+        })
+    }
+
+    /// The nodes that can move to `node`, searching backwards, and what each
+    /// move costs. A node's heading is the direction it's left in, so the
+    /// cost includes turning at `node`.
+    fn predecessors(&self, (point, heading): Node) -> impl Iterator<Item = (Node, usize)> + '_ {
+        let offsets = self.moves.movement.offsets();
+        offsets
+            .iter()
+            .enumerate()
+            .filter_map(move |(i, &(dx, dy))| {
+                let previous = self.offset(point, (-dx, -dy))?;
+                let previous_heading = self.heading(i);
+                let cost = self.cost(previous, point, previous_heading, heading)?;
+                Some(((previous, previous_heading), cost))
+            })
+    }
+
+    /// A lower bound on the cost from any point to `goal`: it takes at least
+    /// a certain number of moves, and every move costs at least a certain
+    /// amount. It never drops by more than one move's cost per move, so A*
+    /// doesn't need to revisit points.
+    fn heuristic(&self, (goal_x, goal_y): Point) -> impl Fn(Point) -> usize + '_ {
+        let min_step = self.moves.cost.min_step(self.risks.min_risk());
+        let movement = &self.moves.movement;
+        move |(x, y)| {
+            min_step.saturating_mul(movement.min_moves(x.abs_diff(goal_x), y.abs_diff(goal_y)))
+        }
+    }
+
+    /// Dijkstra's algorithm, or A* if there's a `heuristic`.
+    /// https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm
+    /// https://en.wikipedia.org/wiki/A*_search_algorithm
+    ///
+    /// The binary heap version is basically copied (by hand, at least...) from
+    /// https://doc.rust-lang.org/std/collections/binary_heap/index.html
+    fn best_first<Q: Queue>(
+        &self,
+        start: Point,
+        goal: Point,
+        heuristic: impl Fn(Point) -> usize,
+    ) -> Option<Path> {
+        let mut distances = HashMap::from([((start, 0), 0usize)]);
+        // The node each node was reached from on its best path so far.
+        let mut previous = HashMap::new();
+        let mut queue = Q::default();
+        queue.push(heuristic(start), (start, 0));
+
+        while let Some((priority, node)) = queue.pop() {
+            let distance = distances[&node];
+            if priority > distance.saturating_add(heuristic(node.0)) {
+                // There's a shorter path to this node than when it was queued.
+                continue;
+            }
+            if node.0 == goal {
+                return Some(Path::new(&previous, node, None, distance));
+            }
+
+            for (neighbor, cost) in self.successors(node) {
+                // Paths that cost too much to count can't lead anywhere.
+                let next = match distance.checked_add(cost) {
+                    Some(next) => next,
+                    None => continue,
+                };
+                let priority = match next.checked_add(heuristic(neighbor.0)) {
+                    Some(priority) => priority,
+                    None => continue,
+                };
+                if distances.get(&neighbor).is_none_or(|&d| next < d) {
+                    distances.insert(neighbor, next);
+                    previous.insert(neighbor, node);
+                    queue.push(priority, neighbor);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Dijkstra's algorithm from both ends at once, stopping once nothing left in
+    /// either queue could improve on the best meeting point.
+    fn bidirectional<Q: Queue>(&self, start: Point, goal: Point) -> Option<Path> {
+        let mut forward = HashMap::from([((start, 0), 0)]);
+        let mut backward = HashMap::from([((goal, 0), 0)]);
+        let mut previous = HashMap::new();
+        let mut next = HashMap::new();
+        let mut forward_queue = Q::default();
+        let mut backward_queue = Q::default();
+        forward_queue.push(0, (start, 0));
+        backward_queue.push(0, (goal, 0));
+
+        // The cost of the best path so far, and where its halves meet.
+        let mut best = (start == goal).then_some((0, (start, 0), (goal, 0)));
+
+        while let (Some(forward_min), Some(backward_min)) =
+            (forward_queue.peek(), backward_queue.peek())
+        {
+            if best.is_some_and(|(cost, _, _)| forward_min.saturating_add(backward_min) >= cost) {
+                break;
+            }
+
+            let is_forward = forward_min <= backward_min;
+            let (queue, distances, links, other_distances) = if is_forward {
+                (&mut forward_queue, &mut forward, &mut previous, &backward)
+            } else {
+                (&mut backward_queue, &mut backward, &mut next, &forward)
+            };
+            let (distance, node) = queue.pop().unwrap();
+            if distances.get(&node).is_some_and(|&d| distance > d) {
+                continue;
+            }
+
+            let neighbors = if is_forward {
+                Either::Left(self.successors(node))
+            } else {
+                Either::Right(self.predecessors(node))
+            };
+            for (neighbor, cost) in neighbors {
+                let next_distance = match distance.checked_add(cost) {
+                    Some(next_distance) => next_distance,
+                    None => continue,
+                };
+                if distances.get(&neighbor).is_none_or(|&d| next_distance < d) {
+                    distances.insert(neighbor, next_distance);
+                    links.insert(neighbor, node);
+                    queue.push(next_distance, neighbor);
+                }
+
+                // Join this half to every heading the other half has reached this
+                // point with.
+                let d = distances[&neighbor];
+                let point = neighbor.0;
+                for other_heading in 0..self.headings {
+                    let other_node = (point, other_heading);
+                    if let Some(&other) = other_distances.get(&other_node) {
+                        let (forward_node, backward_node) = if is_forward {
+                            (neighbor, other_node)
+                        } else {
+                            (other_node, neighbor)
+                        };
+                        let cost = d.checked_add(other).and_then(|cost| {
+                            cost.checked_add(self.turn_cost(forward_node.1, backward_node.1))
+                        });
+                        if let Some(cost) = cost {
+                            if best.is_none_or(|(best_cost, _, _)| cost < best_cost) {
+                                best = Some((cost, forward_node, backward_node));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let (cost, forward_node, backward_node) = best?;
+        Some(Path::new(
+            &previous,
+            forward_node,
+            Some((&next, backward_node)),
+            cost,
+        ))
+    }
+}
+
+/// A route through a [`RiskMap`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path {
+    /// The total cost of the path's moves. With the puzzle's moves, that's
+    /// the sum of the risks of every point on the path except the start,
+    /// which isn't entered.
+    pub cost: usize,
+    /// From the start to the goal, inclusive.
+    pub points: Vec<Point>,
+}
+
+impl Path {
+    /// Follow `previous` back from `middle` to the start, and then the
+    /// backward search's `next` links (if there are any) forward from the
+    /// same point to the goal.
+    fn new(
+        previous: &HashMap<Node, Node>,
+        middle: Node,
+        next: Option<(&HashMap<Node, Node>, Node)>,
+        cost: usize,
+    ) -> Self {
+        let mut node = middle;
+        let mut points = vec![node.0];
+        while let Some(&previous) = previous.get(&node) {
+            points.push(previous.0);
+            node = previous;
+        }
+        points.reverse();
+        if let Some((next, mut node)) = next {
+            while let Some(&next) = next.get(&node) {
+                points.push(next.0);
+                node = next;
+            }
+        }
+        Self { cost, points }
+    }
+}
+
+/// How [`RiskMap::render_path`] shows which points are on the path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Highlight {
+    /// Points off the path are drawn as `.`.
+    Dots,
+    /// Points on the path are bright and the rest are dim, for a terminal.
+    Ansi,
+}
+
+/// How [`RiskMap::find_path_with`] searches. The default is the fastest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Search {
+    pub algorithm: Algorithm,
+    pub queue: QueueKind,
+}
+
+impl Search {
+    pub const ALL: [Search; 6] = [
+        Search::new(Algorithm::Dijkstra, QueueKind::BinaryHeap),
+        Search::new(Algorithm::Dijkstra, QueueKind::Buckets),
+        Search::new(Algorithm::AStar, QueueKind::BinaryHeap),
+        Search::new(Algorithm::AStar, QueueKind::Buckets),
+        Search::new(Algorithm::Bidirectional, QueueKind::BinaryHeap),
+        Search::new(Algorithm::Bidirectional, QueueKind::Buckets),
+    ];
+
+    pub const fn new(algorithm: Algorithm, queue: QueueKind) -> Self {
+        Self { algorithm, queue }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    #[default]
+    Dijkstra,
+    /// Dijkstra's algorithm, guided towards the goal by the fewest moves
+    /// it could take to get there, times the cheapest a move could be.
+    AStar,
+    /// Dijkstra's algorithm from the start and the goal at once.
+    Bidirectional,
+}
+
+/// The priority queue of points to visit next.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QueueKind {
+    BinaryHeap,
+    /// A bucket for each priority. Risks are only 1 to 9, so the next
+    /// priority is always in one of the next few buckets. There's a bucket
+    /// for every priority up to the goal's, though, so costs in the
+    /// millions need a binary heap instead.
+    #[default]
+    Buckets,
+}
+
+/// A min-priority queue of nodes, where priorities only ever go up.
+trait Queue: Default {
+    fn push(&mut self, priority: usize, node: Node);
+    fn pop(&mut self) -> Option<(usize, Node)>;
+    /// The lowest priority in the queue.
+    fn peek(&mut self) -> Option<usize>;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct State {
+    priority: usize,
+    index: Node,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Reversed ordering to make a min-heap.
+        other
+            .priority
+            .cmp(&self.priority)
+            // Tie-breaker for consistency with Eq.
+            .then_with(|| self.index.cmp(&other.index))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Queue for BinaryHeap<State> {
+    fn push(&mut self, priority: usize, index: Node) {
+        BinaryHeap::push(self, State { priority, index });
+    }
+
+    fn pop(&mut self) -> Option<(usize, Node)> {
+        BinaryHeap::pop(self).map(|state| (state.priority, state.index))
+    }
+
+    fn peek(&mut self) -> Option<usize> {
+        BinaryHeap::peek(self).map(|state| state.priority)
+    }
+}
+
+/// Dial's algorithm: a bucket of nodes for each priority, and a cursor at
+/// the lowest non-empty bucket.
+#[derive(Default)]
+struct BucketQueue {
+    buckets: Vec<Vec<Node>>,
+    cursor: usize,
+}
+
+impl Queue for BucketQueue {
+    fn push(&mut self, priority: usize, node: Node) {
+        debug_assert!(priority >= self.cursor, "Priorities can't go down");
+        if priority >= self.buckets.len() {
+            self.buckets.resize_with(priority + 1, Vec::new);
+        }
+        self.buckets[priority].push(node);
+    }
+
+    fn pop(&mut self) -> Option<(usize, Node)> {
+        self.peek()?;
+        self.buckets[self.cursor]
+            .pop()
+            .map(|node| (self.cursor, node))
+    }
+
+    fn peek(&mut self) -> Option<usize> {
+        while self.buckets.get(self.cursor)?.is_empty() {
+            self.cursor += 1;
+        }
+        Some(self.cursor)
+    }
+}
+
+type Input = RiskMap;
+
+pub fn part_1(input: Input) -> usize {
+    input.shortest_path()
+}
+
+pub fn part_2(input: Input) -> usize {
+    input.tiled(FACTOR).shortest_path()
+}
+
+pub struct Chiton;
+
+impl Solution for Chiton {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 15;
+    const NAME: &'static str = "day_15_chiton";
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part_2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+
+    use super::*;
+
+    fn sample() -> Input {
+        "1163751742\n\
+        1381373672\n\
+        2136511328\n\
+        3694931569\n\
+        7463417111\n\
+        1319128137\n\
+        1359912421\n\
+        3125421639\n\
+        1293138521\n\
+        2311944581"
+            .parse()
+            .unwrap()
+    }
+
+    fn input() -> Input {
+        input!(2021, "day_15_chiton").parse().unwrap()
+    }
+
+    #[test]
+    fn test_part_1_sample() {
+        assert_eq!(part_1(sample()), 40);
+    }
+
+    #[test]
+    fn test_find_path() {
+        let map = sample();
+        let path = map.find_path((0, 0), (9, 9)).unwrap();
+        assert_eq!(path.cost, 40);
+        assert_eq!(path.points.len(), 19);
+        assert_eq!(
+            path.points
+                .iter()
+                .skip(1)
+                .map(|&p| map.risk(p) as usize)
+                .sum::<usize>(),
+            40
+        );
+        assert!(path
+            .points
+            .iter()
+            .tuple_windows()
+            .all(|(&(x1, y1), &(x2, y2))| x1.abs_diff(x2) + y1.abs_diff(y2) == 1));
+        assert_eq!(
+            map.render_path(&path, Highlight::Dots),
+            "1.........\n\
+             1.........\n\
+             2136511...\n\
+             ......15..\n\
+             .......1..\n\
+             .......13.\n\
+             ........2.\n\
+             ........3.\n\
+             ........21\n\
+             .........1\n"
+        );
+
+        // Going backwards enters the top left instead of the bottom right,
+        // which both have a risk of 1.
+        assert_eq!(map.find_path((9, 9), (0, 0)).unwrap().cost, 40);
+        assert_eq!(
+            map.find_path((2, 3), (2, 3)),
+            Some(Path {
+                cost: 0,
+                points: vec![(2, 3)]
+            })
+        );
+        assert_eq!(map.find_path((0, 0), (10, 0)), None);
+    }
+
+    /// Check that every search finds a valid path with the same cost as
+    /// plain Dijkstra's algorithm between a few pairs of points.
+    fn assert_searches_agree<M, C>(map: &RiskMap, moves: &Moves<M, C>)
+    where
+        M: Movement + Debug,
+        C: Cost + Debug,
+    {
+        let (width, height) = (map.width(), map.height());
+        let ends = [
+            ((0, 0), (width - 1, height - 1)),
+            ((width - 1, height - 1), (0, 0)),
+            ((width / 2, 0), (width / 3, height - 1)),
+            ((width - 1, height / 4), (1, height / 2)),
+            ((3, 5), (3, 5)),
+            ((3, 5), (4, 5)),
+        ];
+        for (start, goal) in ends {
+            let expected = map
+                .find_path_by(
+                    start,
+                    goal,
+                    moves,
+                    Search::new(Algorithm::Dijkstra, QueueKind::BinaryHeap),
+                )
+                .unwrap();
+            for search in Search::ALL {
+                let context = format!("{:?} {:?} from {:?} to {:?}", moves, search, start, goal);
+                let path = map.find_path_by(start, goal, moves, search).unwrap();
+                assert_eq!(path.cost, expected.cost, "{}", context);
+                assert_eq!(path.points.first(), Some(&start), "{}", context);
+                assert_eq!(path.points.last(), Some(&goal), "{}", context);
+                assert_eq!(
+                    moves.cost_of(map, &path.points),
+                    Some(path.cost),
+                    "{}",
+                    context
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_searches_agree() {
+        for map in [sample(), sample().expanded(), input()] {
+            assert_searches_agree(&map, &Moves::PUZZLE);
+        }
+    }
+
+    #[test]
+    fn test_moves_searches_agree() {
+        fn with_every_cost<M: Movement + Copy + Debug>(map: &RiskMap, movement: M) {
+            assert_searches_agree(
+                map,
+                &Moves {
+                    movement,
+                    cost: Entering,
+                },
+            );
+            assert_searches_agree(
+                map,
+                &Moves {
+                    movement,
+                    cost: Average,
+                },
+            );
+            assert_searches_agree(
+                map,
+                &Moves {
+                    movement,
+                    cost: TurnPenalty(3),
+                },
+            );
+        }
+        for map in [sample(), sample().expanded()] {
+            with_every_cost(&map, FourWay);
+            with_every_cost(&map, EightWay);
+            with_every_cost(&map, Knight);
+        }
+    }
+
+    fn path<M: Movement, C: Cost>(
+        map: &str,
+        movement: M,
+        cost: C,
+        start: Point,
+        goal: Point,
+    ) -> Option<Path> {
+        map.parse::<RiskMap>().unwrap().find_path_by(
+            start,
+            goal,
+            &Moves { movement, cost },
+            Search::default(),
+        )
+    }
+
+    #[test]
+    fn test_moves() {
+        let diagonal = path("199\n919\n991", EightWay, Entering, (0, 0), (2, 2)).unwrap();
+        assert_eq!(diagonal.points, [(0, 0), (1, 1), (2, 2)]);
+        assert_eq!(diagonal.cost, 2);
+
+        assert_eq!(
+            path("123\n456\n789", Knight, Entering, (0, 0), (2, 1))
+                .unwrap()
+                .cost,
+            6
+        );
+        assert_eq!(path("1111", Knight, Entering, (0, 0), (3, 0)), None);
+
+        // Going down then right costs (1 + 1) + (1 + 1) instead of the
+        // (1 + 9) + (9 + 1) of going right then down.
+        assert_eq!(
+            path("19\n11", FourWay, Average, (0, 0), (1, 1))
+                .unwrap()
+                .cost,
+            4
+        );
+
+        // Every path has to turn at least once.
+        let turning = path("111\n111\n111", FourWay, TurnPenalty(5), (0, 0), (2, 2)).unwrap();
+        assert_eq!(turning.cost, 4 + 5);
+        let turns = Moves {
+            movement: FourWay,
+            cost: TurnPenalty(5),
+        };
+        let ones = RiskMap(Grid::new(3, 3, 1));
+        assert_eq!(turns.cost_of(&ones, &turning.points), Some(4 + 5));
+        assert_eq!(
+            turns.cost_of(&ones, &[(0, 0), (1, 0), (1, 1), (2, 1), (2, 2)]),
+            Some(4 + 3 * 5)
+        );
+        assert_eq!(turns.cost_of(&ones, &[(0, 0), (1, 1)]), None);
+    }
+
+    #[test]
+    fn test_custom_moves() {
+        // Only right or down, with the cost of leaving a point instead of
+        // entering it.
+        let right_or_down = vec![(1, 0), (0, 1)];
+        let leaving = |from: u8, _to: u8| usize::from(from);
+        let leave = path("19\n11", right_or_down.clone(), leaving, (0, 0), (1, 1)).unwrap();
+        assert_eq!(leave.points, [(0, 0), (0, 1), (1, 1)]);
+        assert_eq!(leave.cost, 2);
+        assert_eq!(path("19\n11", right_or_down, leaving, (1, 1), (0, 0)), None);
+    }
+
+    #[test]
+    fn test_cost_overflow() {
+        let huge = |_from: u8, _to: u8| usize::MAX;
+        for algorithm in [
+            Algorithm::Dijkstra,
+            Algorithm::AStar,
+            Algorithm::Bidirectional,
+        ] {
+            let search = Search::new(algorithm, QueueKind::BinaryHeap);
+            let moves = Moves {
+                movement: FourWay,
+                cost: huge,
+            };
+            let map: RiskMap = "111".parse().unwrap();
+            assert_eq!(
+                map.find_path_by((0, 0), (1, 0), &moves, search)
+                    .map(|path| path.cost),
+                Some(usize::MAX)
+            );
+            assert_eq!(map.find_path_by((0, 0), (2, 0), &moves, search), None);
+        }
+        // Every path has to turn, and turning costs too much.
+        assert_eq!(
+            path("11\n11", FourWay, TurnPenalty(usize::MAX), (0, 0), (1, 1)),
+            None
+        );
+        let turns = Moves {
+            movement: FourWay,
+            cost: TurnPenalty(usize::MAX),
+        };
+        let ones = RiskMap(Grid::new(2, 2, 1));
+        assert_eq!(turns.cost_of(&ones, &[(0, 0), (1, 0), (1, 1)]), None);
+    }
+
+    #[test]
+    fn test_tiled() {
+        let map = sample();
+        let expanded = map.expanded();
+        let tiled = map.tiled(5);
+        assert_eq!((tiled.width(), tiled.height()), (50, 50));
+        for (point, &risk) in expanded.0.enumerate() {
+            assert_eq!(tiled.risk(point), risk);
+        }
+        assert_eq!(tiled.risk((49, 49)), 9);
+        assert_eq!(map.tiled(1).shortest_path(), 40);
+        assert_eq!(tiled.shortest_path(), 315);
+
+        for factor in 1..=6 {
+            let tiled = map.tiled(factor);
+            assert_eq!(
+                tiled.min_risk(),
+                (0..tiled.height())
+                    .cartesian_product(0..tiled.width())
+                    .map(|(y, x)| tiled.risk((x, y)))
+                    .min()
+                    .unwrap()
+            );
+        }
+        assert_eq!(RiskMap(Grid::new(2, 2, 5)).tiled(3).min_risk(), 5);
+
+        // Searches only store the points they reach, so a path near the
+        // corner of a map with a trillion points is quick to find.
+        let huge = map.tiled(100_000);
+        for search in Search::ALL {
+            assert_eq!(
+                huge.find_path_with((0, 0), (9, 9), search),
+                tiled.find_path_with((0, 0), (9, 9), search),
+                "{:?}",
+                search
+            );
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(input()), 458);
+    }
+
+    #[test]
+    fn test_part_2_sample() {
+        assert_eq!(part_2(sample()), 315);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(input()), 2800);
+    }
+}
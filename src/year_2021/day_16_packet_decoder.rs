@@ -0,0 +1,478 @@
+use std::{
+    ops::{Add, Shl},
+    str::FromStr,
+};
+
+use crate::*;
+use bitvec::prelude::*;
+use tap::Tap;
+
+type Bytes = BitSlice<Msb0, u8>;
+
+fn str_to_bits(data: &str) -> Result<BitVec<Msb0, u8>, Error> {
+    let digits = data
+        .char_indices()
+        .map(|(i, c)| c.parse().map_err(|kind| Error::at(data, &data[i..], kind)))
+        .collect::<Result<Vec<u8>, _>>()?;
+    if digits.len() % 2 != 0 {
+        return Err(Error::after(
+            data,
+            data,
+            ErrorKind::UnexpectedEnd {
+                expected: "an even number of hex digits",
+            },
+        ));
+    }
+    Ok(BitVec::from_vec(
+        digits
+            .into_iter()
+            .tuples()
+            .map(|(hi, lo)| hi << 4 | lo)
+            .collect(),
+    ))
+}
+
+/// An error at `at`, a subslice of the whole transmission `source`. Positions
+/// are reported in hex digits, since that's how the transmission is written.
+fn error_at(source: &Bytes, at: &Bytes, kind: ErrorKind) -> Error {
+    Error::at_position(
+        Position {
+            line: 1,
+            column: source.offset_from(at) as usize / 4 + 1,
+        },
+        kind,
+    )
+}
+
+/// Split the first `bits` bits off of `data`.
+fn take<'a>(source: &Bytes, data: &'a Bytes, bits: usize) -> Result<(&'a Bytes, &'a Bytes), Error> {
+    if data.len() < bits {
+        Err(error_at(
+            source,
+            &data[data.len()..],
+            ErrorKind::UnexpectedEnd {
+                expected: "the rest of the packet",
+            },
+        ))
+    } else {
+        Ok(data.split_at(bits))
+    }
+}
+
+const MIN_PACKET_SIZE: usize = 11;
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+impl TryFrom<u8> for Operator {
+    type Error = ErrorKind;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use Operator::*;
+        match value {
+            0 => Ok(Sum),
+            1 => Ok(Product),
+            2 => Ok(Minimum),
+            3 => Ok(Maximum),
+            5 => Ok(GreaterThan),
+            6 => Ok(LessThan),
+            7 => Ok(EqualTo),
+            _ => Err(ErrorKind::UnexpectedToken {
+                found: value.to_string(),
+                expected: "an operator type ID",
+            }),
+        }
+    }
+}
+
+impl From<Operator> for u8 {
+    fn from(value: Operator) -> Self {
+        // Safety: `Operator` is `#[repr(u8)]`
+        unsafe { std::mem::transmute(value) }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Packet {
+    version: u8, // 3 bits
+    data: PacketData,
+}
+
+impl Packet {
+    pub fn get_value(&self) -> Result<usize, Error> {
+        match &self.data {
+            PacketData::Literal(value) => Ok(*value),
+            PacketData::Operator(operator, inner) => {
+                let values = inner
+                    .iter()
+                    .map(Packet::get_value)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let compare = |compare: fn(&usize, &usize) -> bool| match values[..] {
+                    [a, b] => Ok(compare(&a, &b) as usize),
+                    _ => Err(Error::no_solution(
+                        "a comparison packet doesn't have exactly 2 subpackets",
+                    )),
+                };
+                match operator {
+                    Operator::Sum => values
+                        .iter()
+                        .try_fold(0usize, |sum, value| sum.checked_add(*value))
+                        .ok_or_else(Error::overflow),
+                    Operator::Product => values
+                        .iter()
+                        .try_fold(1usize, |product, value| product.checked_mul(*value))
+                        .ok_or_else(Error::overflow),
+                    Operator::Minimum => values
+                        .iter()
+                        .copied()
+                        .min()
+                        .ok_or_else(|| Error::no_solution("a minimum packet has no subpackets")),
+                    Operator::Maximum => values
+                        .iter()
+                        .copied()
+                        .max()
+                        .ok_or_else(|| Error::no_solution("a maximum packet has no subpackets")),
+                    Operator::GreaterThan => compare(usize::gt),
+                    Operator::LessThan => compare(usize::lt),
+                    Operator::EqualTo => compare(usize::eq),
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Packet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        str_to_bits(s)?.as_bitslice().try_into()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PacketData {
+    Literal(usize),
+    Operator(Operator, Vec<Packet>),
+}
+
+#[derive(Debug)]
+enum PacketLength {
+    Bits(usize),
+    Count(usize),
+}
+
+fn parse_packets<'a>(
+    source: &Bytes,
+    data: &'a Bytes,
+    length: PacketLength,
+) -> Result<(Vec<Packet>, &'a Bytes), Error> {
+    let mut rest = data;
+    let mut ret = Vec::new();
+
+    let should_keep_parsing_packets: Box<dyn Iterator<Item = usize>> = match length {
+        PacketLength::Bits(bits) => {
+            rest = take(source, rest, bits)?.0;
+            Box::new(std::iter::repeat(0))
+        }
+
+        PacketLength::Count(count) => {
+            ret = Vec::with_capacity(count);
+            Box::new(0..count)
+        }
+    };
+
+    for _ in should_keep_parsing_packets {
+        if rest.len() < MIN_PACKET_SIZE {
+            break;
+        }
+        let (packet, next_rest) = parse_one_packet(source, rest)?;
+        ret.push(packet);
+        rest = next_rest;
+    }
+
+    Ok((
+        ret,
+        match length {
+            PacketLength::Bits(bits) => &data[bits..],
+            PacketLength::Count(_) => rest,
+        },
+    ))
+}
+
+fn parse_one_packet<'a>(source: &Bytes, data: &'a Bytes) -> Result<(Packet, &'a Bytes), Error> {
+    // Why isn't this in bitvec...?
+    fn bits_to_int<T>(bits: &Bytes) -> T
+    where
+        T: Shl<Output = T> + Add<Output = T> + From<u8>,
+    {
+        bits.iter()
+            .by_val()
+            .map(|b| if b { 1.into() } else { 0.into() })
+            .fold(0.into(), |acc, el| (acc << 1.into()) + el)
+    }
+
+    let (version, rest) = take(source, data, 3)?;
+    let version = bits_to_int(version);
+    let (type_id_bits, mut rest) = take(source, rest, 3)?;
+    let type_id = bits_to_int(type_id_bits);
+
+    if let 4 = type_id {
+        let mut num: usize = 0;
+        // Type ID 4 indicates a literal value (single binary number)
+        const CHUNK_SIZE: usize = 5;
+        loop {
+            let (chunk, next_rest) = take(source, rest, CHUNK_SIZE)?;
+            if num.leading_zeros() < 4 {
+                return Err(error_at(source, chunk, ErrorKind::IntegerOverflow));
+            }
+            num <<= 4;
+            num += bits_to_int::<usize>(&chunk[1..5]);
+            rest = next_rest;
+            if !chunk[0] {
+                break;
+            }
+        }
+        let rest = if rest.len() < MIN_PACKET_SIZE {
+            &rest[rest.len()..]
+        } else {
+            rest
+        };
+        Ok((
+            Packet {
+                version,
+                data: PacketData::Literal(num),
+            },
+            rest,
+        ))
+    } else {
+        // All other type IDs indicate an operator
+        let operator = type_id
+            .try_into()
+            .map_err(|kind| error_at(source, type_id_bits, kind))?;
+        let (length_type_id, rest) = take(source, rest, 1)?;
+        let (length, rest) = if length_type_id[0] {
+            // 1: next 11 bits represent number of sub-packets
+            let (subpackets_count, rest) = take(source, rest, 11)?;
+            (
+                PacketLength::Count(bits_to_int::<u16>(subpackets_count) as usize),
+                rest,
+            )
+        } else {
+            // 0: next 15 bits represent total length in bits of sub-packets
+            let (subpackets_size, rest) = take(source, rest, 15)?;
+            (
+                PacketLength::Bits(bits_to_int::<u16>(subpackets_size) as usize),
+                rest,
+            )
+        };
+        let (packets, rest) = parse_packets(source, rest, length)?;
+        Ok((
+            Packet {
+                version,
+                data: PacketData::Operator(operator, packets),
+            },
+            rest,
+        ))
+    }
+}
+
+impl TryFrom<&BitSlice<Msb0, u8>> for Packet {
+    type Error = Error;
+
+    fn try_from(data: &BitSlice<Msb0, u8>) -> Result<Self, Self::Error> {
+        let (packet, rest) = parse_one_packet(data, data)?;
+        if rest.any() {
+            Err(error_at(data, rest, ErrorKind::TrailingInput))
+        } else {
+            Ok(packet)
+        }
+    }
+}
+
+type Input = Packet;
+
+pub fn part_1(input: Input) -> usize {
+    input.version as usize
+        + match input.data {
+            PacketData::Literal(_) => 0,
+            PacketData::Operator(_, packets) => packets.into_iter().map(part_1).sum(),
+        }
+}
+
+pub fn part_2(input: Input) -> Result<usize, Error> {
+    input.get_value()
+}
+
+pub struct PacketDecoder;
+
+impl Solution for PacketDecoder {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 16;
+    const NAME: &'static str = "day_16_packet_decoder";
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.trim().parse()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input() -> Input {
+        input!(2021, "day_16_packet_decoder")
+            .trim()
+            .parse()
+            .unwrap()
+    }
+
+    fn parse_packet(data: &str) -> Packet {
+        data.parse().unwrap()
+    }
+
+    #[test]
+    #[allow(clippy::useless_transmute)] // Expanded from `bitvec!`.
+    fn test_str_to_bits() {
+        assert_eq!(
+            str_to_bits("D2FE28").unwrap(),
+            bitvec![
+                Msb0, u8;
+                1, 1, 0, 1, 0, 0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 1, 0, 1,
+                0, 0, 0,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let packet: Packet = parse_packet("D2FE28");
+        assert_eq!(
+            packet,
+            Packet {
+                version: 6,
+                data: PacketData::Literal(2021)
+            }
+        );
+
+        let packet: Packet = parse_packet("38006F45291200");
+        assert_eq!(
+            packet,
+            Packet {
+                version: 1,
+                data: PacketData::Operator(
+                    Operator::LessThan,
+                    vec![
+                        Packet {
+                            version: 6,
+                            data: PacketData::Literal(10),
+                        },
+                        Packet {
+                            version: 2,
+                            data: PacketData::Literal(20),
+                        }
+                    ]
+                )
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = |data: &str| data.parse::<Packet>().unwrap_err().to_string();
+        assert_eq!(
+            error("D2FG28"),
+            "line 1, column 4: unexpected character 'G', expected a digit"
+        );
+        assert_eq!(
+            error("38006F45"),
+            "line 1, column 9: unexpected end of input, expected the rest of the packet"
+        );
+        assert_eq!(
+            error("38006F45291200FF"),
+            "line 1, column 13: unexpected input after the end"
+        );
+    }
+
+    #[test]
+    fn test_part_1_sample() {
+        assert_eq!(part_1(parse_packet("8A004A801A8002F478")), 16);
+        assert_eq!(part_1(parse_packet("620080001611562C8802118E34")), 12);
+        assert_eq!(part_1(parse_packet("C0015000016115A2E0802F182340")), 23);
+        assert_eq!(part_1(parse_packet("A0016C880162017C3686B18A3D4780")), 31);
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(input()), 852);
+    }
+
+    #[test]
+    fn test_part_2_sample() {
+        assert_eq!(part_2(parse_packet("C200B40A82")), Ok(3));
+        assert_eq!(part_2(parse_packet("04005AC33890")), Ok(54));
+        assert_eq!(part_2(parse_packet("880086C3E88112")), Ok(7));
+        assert_eq!(part_2(parse_packet("CE00C43D881120")), Ok(9));
+        assert_eq!(part_2(parse_packet("D8005AC2A8F0")), Ok(1));
+        assert_eq!(part_2(parse_packet("F600BC2D8F")), Ok(0));
+        assert_eq!(part_2(parse_packet("9C005AC2F8F0")), Ok(0));
+        assert_eq!(part_2(parse_packet("9C0141080250320F1802104A08")), Ok(1));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(input()), Ok(19348959966392));
+    }
+
+    #[test]
+    fn test_part_2_overflow() {
+        assert_eq!(
+            part_2(parse_packet(
+                "060084C42108421084210842001210842108421084210810"
+            )),
+            Err(Error::overflow())
+        );
+    }
+
+    #[test]
+    fn test_part_2_wrong_subpacket_count() {
+        let literal = |value| Packet {
+            version: 0,
+            data: PacketData::Literal(value),
+        };
+        let operator = |operator, packets| Packet {
+            version: 0,
+            data: PacketData::Operator(operator, packets),
+        };
+        assert_eq!(
+            part_2(operator(Operator::GreaterThan, vec![literal(1)])),
+            Err(Error::no_solution(
+                "a comparison packet doesn't have exactly 2 subpackets"
+            ))
+        );
+        assert_eq!(
+            part_2(operator(Operator::Minimum, vec![])),
+            Err(Error::no_solution("a minimum packet has no subpackets"))
+        );
+    }
+}
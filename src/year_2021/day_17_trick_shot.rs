@@ -58,35 +58,19 @@ pub struct TargetArea {
 }
 
 impl FromStr for TargetArea {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.trim();
-        let start = "target area: x=";
-        if s.starts_with(start) {
-            let s = &s[start.len()..];
-            let (x_range, y_range) = s
-                .split_once(", y=")
-                .ok_or_else(|| format!("Expected to find ', y=' in {:?}", s))?;
-            let (x_min, x_max) = x_range
-                .split_once("..")
-                .ok_or_else(|| format!("Expected coords delimited by '..': {:?}", x_range))?;
-            let (y_min, y_max) = y_range
-                .split_once("..")
-                .ok_or_else(|| format!("Expected coords delimited by '..': {:?}", y_range))?;
-            let parse = |num: &str| {
-                num.parse::<isize>()
-                    .map_err(|err| format!("{}: {:?}", err, num))
-            };
-            Ok(Self {
-                x_min: parse(x_min)?,
-                x_max: parse(x_max)?,
-                y_min: parse(y_min)?,
-                y_max: parse(y_max)?,
-            })
-        } else {
-            Err(format!("Expected {:?} to start with {:?}", s, start))
-        }
+        let target_area = expect_prefix(s, s.trim(), "target area: x=")?;
+        let (x_range, y_range) = expect_split(s, target_area, ", y=")?;
+        let (x_min, x_max) = expect_split(s, x_range, "..")?;
+        let (y_min, y_max) = expect_split(s, y_range, "..")?;
+        Ok(Self {
+            x_min: parse_int(s, x_min)?,
+            x_max: parse_int(s, x_max)?,
+            y_min: parse_int(s, y_min)?,
+            y_max: parse_int(s, y_max)?,
+        })
     }
 }
 
@@ -130,6 +114,30 @@ pub fn part_2(input: Input) -> usize {
     distinct_pairs.len()
 }
 
+pub struct TrickShot;
+
+impl Solution for TrickShot {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 17;
+    const NAME: &'static str = "day_17_trick_shot";
+
+    type Input = Input;
+    type Answer1 = isize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part_2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,7 +152,7 @@ mod tests {
     }
 
     fn input() -> Input {
-        input!("day_17_trick_shot").parse().unwrap()
+        input!(2021, "day_17_trick_shot").parse().unwrap()
     }
 
     #[test]
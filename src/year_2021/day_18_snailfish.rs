@@ -39,80 +39,61 @@ impl Debug for Snailfish {
 }
 
 impl FromStr for Snailfish {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        fn parse_snailfish_number(input: &str) -> Result<(Snailfish, &str), String> {
-            if input.chars().next().ok_or_else(|| {
-                "Expected non-empty string when parsing snailfish number".to_owned()
-            })? == '['
-            {
-                parse_pair(input)
-            } else {
-                parse_regular_number(input)
+        fn parse_snailfish_number<'a>(
+            source: &str,
+            input: &'a str,
+        ) -> Result<(Snailfish, &'a str), Error> {
+            match input.chars().next() {
+                Some('[') => parse_pair(source, input),
+                Some(_) => parse_regular_number(source, input),
+                None => Err(Error::at(
+                    source,
+                    input,
+                    ErrorKind::UnexpectedEnd {
+                        expected: "a snailfish number",
+                    },
+                )),
             }
         }
 
-        fn parse_pair(input: &str) -> Result<(Snailfish, &str), String> {
-            let input = parse_open_bracket(input)?;
-            let (left, input) = parse_snailfish_number(input)?;
-            let input = parse_comma(input)?;
-            let (right, input) = parse_snailfish_number(input)?;
-            let input = parse_close_bracket(input)?;
+        fn parse_pair<'a>(source: &str, input: &'a str) -> Result<(Snailfish, &'a str), Error> {
+            let input = expect_prefix(source, input, "[")?;
+            let (left, input) = parse_snailfish_number(source, input)?;
+            let input = expect_prefix(source, input, ",")?;
+            let (right, input) = parse_snailfish_number(source, input)?;
+            let input = expect_prefix(source, input, "]")?;
             Ok((Snailfish::new_pair(left, right), input))
         }
 
-        fn parse_comma(input: &str) -> Result<&str, String> {
-            if input.starts_with(',') {
-                Ok(&input[1..])
-            } else {
-                Err(format!(
-                    "Failed to parse pair (no comma) starting at: {:?}",
-                    input
-                ))
-            }
-        }
-
-        fn parse_open_bracket(input: &str) -> Result<&str, String> {
-            if input.starts_with('[') {
-                Ok(&input[1..])
-            } else {
-                Err(format!(
-                    "Failed to parse pair (no open bracket) starting at: {:?}",
-                    input
-                ))
-            }
-        }
-
-        fn parse_close_bracket(input: &str) -> Result<&str, String> {
-            if input.starts_with(']') {
-                Ok(&input[1..])
-            } else {
-                Err(format!(
-                    "Failed to parse pair (no close bracket) at: {:?}",
-                    input
-                ))
-            }
-        }
-
-        fn parse_regular_number(input: &str) -> Result<(Snailfish, &str), String> {
+        fn parse_regular_number<'a>(
+            source: &str,
+            input: &'a str,
+        ) -> Result<(Snailfish, &'a str), Error> {
             let parse_len = input
-                .char_indices()
-                .take_while(|(_index, c)| c.is_ascii_digit())
-                .map(|(index, c)| index + c.len_utf8())
-                .last()
-                .ok_or_else(|| format!("Failed to parse number starting at: {:?}", input))?;
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(input.len());
             if parse_len == 0 {
-                return Err(format!("Failed to find number starting at: {:?}", input));
+                let found = input.chars().next().unwrap_or_default();
+                return Err(Error::at(
+                    source,
+                    input,
+                    ErrorKind::UnexpectedChar {
+                        found,
+                        expected: "a digit or '['",
+                    },
+                ));
             }
-            let number_str = &input[..parse_len];
-            let number = number_str
-                .parse()
-                .map_err(|err| format!("{}: {:?}", err, number_str))?;
+            let number = parse_int(source, &input[..parse_len])?;
             Ok((Snailfish::Number(number), &input[parse_len..]))
         }
 
-        Ok(parse_snailfish_number(s)?.0)
+        match parse_snailfish_number(s, s)? {
+            (number, "") => Ok(number),
+            (_, rest) => Err(Error::at(s, rest, ErrorKind::TrailingInput)),
+        }
     }
 }
 
@@ -136,7 +117,7 @@ impl Snailfish {
     }
 
     fn node_from_path_mut(&mut self, path: &[Turn]) -> &mut Self {
-        path.into_iter().fold(self, |node, turn| match node {
+        path.iter().fold(self, |node, turn| match node {
             Snailfish::Number(_) => unreachable!(),
             Snailfish::Pair(a, b) => match turn {
                 Turn::Left => a,
@@ -146,7 +127,7 @@ impl Snailfish {
     }
 
     fn next_prev_number_mut(&mut self, path: &[Turn], find: Turn) -> Option<&mut usize> {
-        let idx = path.len() - 1 - path.into_iter().rev().position(|turn| *turn == find)?;
+        let idx = path.len() - 1 - path.iter().rev().position(|turn| *turn == find)?;
         let node = self.node_from_path_mut(&path[..idx]);
         match node {
             Snailfish::Number(_) => None,
@@ -165,7 +146,7 @@ impl Snailfish {
         self.next_prev_number_mut(path, Turn::Left)
     }
 
-    fn path_to_explode<'a>(&self, path: &'a mut Vec<Turn>) -> bool {
+    fn path_to_explode(&self, path: &mut Vec<Turn>) -> bool {
         match self {
             Snailfish::Number(_) => {
                 path.pop();
@@ -270,17 +251,45 @@ impl Add for Snailfish {
 
 type Input = Vec<Snailfish>;
 
-pub fn part_1(input: Input) -> usize {
-    input.into_iter().fold1(|a, b| a + b).unwrap().magnitude()
+pub fn part_1(input: Input) -> Result<usize, Error> {
+    input
+        .into_iter()
+        .fold1(|a, b| a + b)
+        .map(|sum| sum.magnitude())
+        .ok_or_else(|| Error::no_solution("there are no snailfish numbers"))
 }
 
-pub fn part_2(input: Input) -> usize {
-    (&input)
-        .into_iter()
+pub fn part_2(input: Input) -> Result<usize, Error> {
+    input
+        .iter()
         .cartesian_product(&input)
         .map(|(a, b)| ((*a).clone() + (*b).clone()).magnitude())
         .max()
-        .unwrap()
+        .ok_or_else(|| Error::no_solution("there are no snailfish numbers"))
+}
+
+pub struct SnailfishHomework;
+
+impl Solution for SnailfishHomework {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 18;
+    const NAME: &'static str = "day_18_snailfish";
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_lines(input)
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
@@ -303,26 +312,40 @@ mod tests {
     }
 
     fn input() -> Input {
-        input!("day_18_snailfish", Snailfish).collect()
+        input!(2021, "day_18_snailfish", Snailfish).collect()
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = |number: &str| number.parse::<Snailfish>().unwrap_err().to_string();
+        assert_eq!(error("[[1,2],3"), "line 1, column 9: expected \"]\"");
+        assert_eq!(
+            error("[1,x]"),
+            "line 1, column 4: unexpected character 'x', expected a digit or '['"
+        );
+        assert_eq!(
+            error("[1,2]]"),
+            "line 1, column 6: unexpected input after the end"
+        );
     }
 
     #[test]
     fn test_part_1_sample() {
-        assert_eq!(part_1(sample()), 4140);
+        assert_eq!(part_1(sample()), Ok(4140));
     }
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(input()), 4145);
+        assert_eq!(part_1(input()), Ok(4145));
     }
 
     #[test]
     fn test_part_2_sample() {
-        assert_eq!(part_2(sample()), 3993);
+        assert_eq!(part_2(sample()), Ok(3993));
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(input()), 4855);
+        assert_eq!(part_2(input()), Ok(4855));
     }
 }
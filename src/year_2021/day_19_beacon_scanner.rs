@@ -62,16 +62,22 @@ impl Debug for Point {
 }
 
 impl FromStr for Point {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let nums: Vec<isize> = s
             .split(',')
-            .map(|num| num.parse().map_err(|err| format!("{}: {:?}", err, num)))
+            .map(|num| parse_int(s, num))
             .collect::<Result<_, _>>()?;
         nums.try_conv::<[_; 3]>()
             .map(|[x, y, z]| Self(x, y, z))
-            .map_err(|nums| format!("Expected 3 dimensions, found {} ({:?})", nums.len(), nums))
+            .map_err(|nums| {
+                Error::new(ErrorKind::WrongFieldCount {
+                    what: "coordinates",
+                    expected: 3,
+                    found: nums.len(),
+                })
+            })
     }
 }
 
@@ -114,9 +120,8 @@ impl Point {
 
     /// O(points.len() * log NEIGHBORS)
     ///
-    /// # Panics
-    /// If `points.len() < NEIGHBORS`
-    pub fn nearest_points(&self, points: &[Point]) -> (Distances, Neighborhood) {
+    /// Returns `None` if there aren't `NEIGHBORS` points at distinct distances.
+    pub fn nearest_points(&self, points: &[Point]) -> Option<(Distances, Neighborhood)> {
         // Find nearest points:
         let mut candidates = Vec::<(isize, Point)>::with_capacity(NEIGHBORS + 1);
         for point in points {
@@ -149,13 +154,13 @@ impl Point {
             distances.push(distance);
         }
 
-        (
-            Distances(distances.try_conv::<[_; NEIGHBORS]>().unwrap()),
+        Some((
+            Distances(distances.try_conv::<[_; NEIGHBORS]>().ok()?),
             Neighborhood {
-                point: self.clone(),
-                neighbors: points.try_conv::<[_; NEIGHBORS]>().unwrap(),
+                point: *self,
+                neighbors: points.try_conv::<[_; NEIGHBORS]>().ok()?,
             },
-        )
+        ))
     }
 }
 
@@ -365,18 +370,17 @@ pub struct Neighborhood {
 /// Get it, a suburb of neighborhoods?
 struct Suburb(BTreeMap<Distances, Neighborhood>);
 
-impl From<&[Point]> for Suburb {
-    fn from(points: &[Point]) -> Self {
-        Self(
-            points
-                .iter()
-                .map(|point| point.nearest_points(&points))
-                .collect(),
-        )
+impl Suburb {
+    /// Returns `None` if there are too few points to find each one's
+    /// neighborhood.
+    pub fn new(points: &[Point]) -> Option<Self> {
+        points
+            .iter()
+            .map(|point| point.nearest_points(points))
+            .collect::<Option<_>>()
+            .map(Self)
     }
-}
 
-impl Suburb {
     /// Determine similar points between two suburbs.
     pub fn similar<'s, 'o>(&'s self, other: &'o Suburb) -> Vec<(&'s Point, &'o Point)> {
         self.0
@@ -402,11 +406,13 @@ struct Scanner {
 }
 
 impl Scanner {
-    pub fn new(beacons: &[Point]) -> Self {
-        Scanner {
-            neighborhoods: beacons.into(),
+    /// Returns `None` if there are too few beacons to match this scanner
+    /// with others.
+    pub fn new(beacons: &[Point]) -> Option<Self> {
+        Some(Scanner {
+            neighborhoods: Suburb::new(beacons)?,
             beacons: beacons.iter().cloned().collect(),
-        }
+        })
     }
 
     /// With common points `common`, align this scanner with another.
@@ -444,8 +450,11 @@ impl Scanner {
     }
 }
 
+/// An alignment between two scanners and the beacons they have in common.
+type Connection = (Alignment, Vec<Point>);
+
 /// An adjacency matrix of connections between scanners.
-pub struct AdjacencyMatrix(Vec<Vec<Option<(Alignment, Vec<Point>)>>>);
+pub struct AdjacencyMatrix(Vec<Vec<Option<Connection>>>);
 
 impl AdjacencyMatrix {
     pub fn connections(
@@ -502,7 +511,11 @@ impl Scanners {
     /// Assemble the full beacon-map.
     /// Returns a map of scanner index to alignment (for distances between
     /// scanners) and the completed beacon-map of deduplicated points.
-    pub fn assemble_map(&self) -> (HashMap<usize, Alignment>, HashSet<Point>) {
+    pub fn assemble_map(&self) -> Result<(HashMap<usize, Alignment>, HashSet<Point>), Error> {
+        if self.0.is_empty() {
+            return Err(Error::no_solution("there are no scanners"));
+        }
+
         let adjacencies = self.find_connections();
 
         // Fully-assembled beacon-map.
@@ -542,51 +555,64 @@ impl Scanners {
         }
 
         if explored.len() != self.0.len() {
-            panic!(
-                "Explored {} scanners instead of {}. Missed scanners: {:?}",
-                explored.len(),
-                self.0.len(),
-                (0..self.0.len())
-                    .filter(|i| !explored.contains(i))
-                    .collect::<Vec<_>>()
-            );
+            return Err(Error::no_solution(
+                "some scanners don't overlap with the others",
+            ));
         }
 
-        (alignments, all_beacons)
+        Ok((alignments, all_beacons))
     }
 }
 
 /// Parse puzzle input.
 impl FromStr for Scanners {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut ret = Vec::new();
         let mut points = Vec::with_capacity(64);
 
         let mut new_scanner = true;
+        let mut header = s;
+        let scanner = |header, points: &[Point]| {
+            Scanner::new(points).ok_or_else(|| {
+                Error::at(
+                    s,
+                    header,
+                    ErrorKind::NoSolution("too few beacons to match this scanner with others"),
+                )
+            })
+        };
 
         for line in s.lines() {
             if new_scanner {
                 if !line.starts_with("--- scanner ") || !line.ends_with(" ---") {
-                    return Err(format!("Expected scanner header, found {:?}", line));
+                    return Err(Error::at(
+                        s,
+                        line,
+                        ErrorKind::UnexpectedToken {
+                            found: line.to_owned(),
+                            expected: "a scanner header like \"--- scanner 0 ---\"",
+                        },
+                    ));
                 }
+                header = line;
                 new_scanner = false;
             } else {
                 if line.trim().is_empty() {
                     // Empty line between scanners.
                     new_scanner = true;
-                    ret.push(Scanner::new(&points));
+                    ret.push(scanner(header, &points)?);
                     points.clear();
                 } else {
                     // Collect another point.
-                    points.push(line.parse()?);
+                    points.push(parse_at(s, line)?);
                 }
             }
         }
 
         if !new_scanner {
-            ret.push(Scanner::new(&points));
+            ret.push(scanner(header, &points)?);
         }
 
         Ok(Self(ret))
@@ -595,19 +621,43 @@ impl FromStr for Scanners {
 
 type Input = Scanners;
 
-pub fn part_1(input: Input) -> usize {
-    input.assemble_map().1.len()
+pub fn part_1(input: Input) -> Result<usize, Error> {
+    Ok(input.assemble_map()?.1.len())
 }
 
-pub fn part_2(input: Input) -> usize {
-    let alignments = input.assemble_map().0;
+pub fn part_2(input: Input) -> Result<usize, Error> {
+    let alignments = input.assemble_map()?.0;
     let mut longest_distance = 0;
     for a in alignments.values() {
         for b in alignments.values() {
             longest_distance = longest_distance.max(a.manhattan_distance(b));
         }
     }
-    longest_distance
+    Ok(longest_distance)
+}
+
+pub struct BeaconScanner;
+
+impl Solution for BeaconScanner {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 19;
+    const NAME: &'static str = "day_19_beacon_scanner";
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
@@ -615,30 +665,65 @@ mod tests {
     use super::*;
 
     fn sample() -> Input {
-        input!("day_19_beacon_scanner_sample").parse().unwrap()
+        input!(2021, "day_19_beacon_scanner_sample")
+            .parse()
+            .unwrap()
     }
 
     fn input() -> Input {
-        input!("day_19_beacon_scanner").parse().unwrap()
+        input!(2021, "day_19_beacon_scanner").parse().unwrap()
     }
 
     #[test]
     fn test_part_1_sample() {
-        assert_eq!(part_1(sample()), 79);
+        assert_eq!(part_1(sample()), Ok(79));
     }
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(input()), 491);
+        assert_eq!(part_1(input()), Ok(491));
     }
 
     #[test]
     fn test_part_2_sample() {
-        assert_eq!(part_2(sample()), 3621);
+        assert_eq!(part_2(sample()), Ok(3621));
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(input()), 13374);
+        assert_eq!(part_2(input()), Ok(13374));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            "--- scanner 0 ---\n1,2,3\n4,5,6\n"
+                .parse::<Scanners>()
+                .err()
+                .map(|err| err.to_string()),
+            Some(
+                "line 1, column 1: no solution: \
+                 too few beacons to match this scanner with others"
+                    .to_owned()
+            )
+        );
+
+        let scanners: Scanners = "--- scanner 0 ---\n\
+                                  0,0,0\n1,0,0\n3,0,0\n7,0,0\n15,0,0\n\
+                                  \n\
+                                  --- scanner 1 ---\n\
+                                  0,0,0\n0,2,0\n0,5,0\n0,9,0\n0,14,0\n"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            part_1(scanners),
+            Err(Error::no_solution(
+                "some scanners don't overlap with the others"
+            ))
+        );
+        assert_eq!(
+            part_1(Scanners(Vec::new())),
+            Err(Error::no_solution("there are no scanners"))
+        );
     }
 }
@@ -0,0 +1,69 @@
+use crate::*;
+
+pub fn part_1(depths: impl Iterator<Item = u16>) -> usize {
+    depths.tuple_windows().filter(|(a, b)| a < b).count()
+}
+
+pub fn part_2(depths: impl Iterator<Item = u16>) -> usize {
+    depths
+        .tuple_windows()
+        .map(|(a, b, c)| u32::from(a) + u32::from(b) + u32::from(c))
+        .tuple_windows()
+        .filter(|(prev, next)| prev < next)
+        .count()
+}
+
+pub struct SonarSweep;
+
+impl Solution for SonarSweep {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 1;
+    const NAME: &'static str = "day_1_sonar_sweep";
+
+    type Input = Vec<u16>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.lines().map(|line| parse_int(input, line)).collect()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input.into_iter()))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part_2(input.into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const SAMPLE: [u16; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_part_1_sample() {
+        assert_eq!(part_1(SAMPLE.into_iter()), 7);
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(input!(2021, "day_1_sonar_sweep", u16)), 1215);
+    }
+
+    #[test]
+    fn test_part_2_sample() {
+        assert_eq!(part_2(SAMPLE.into_iter()), 5);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(input!(2021, "day_1_sonar_sweep", u16)), 1150);
+    }
+
+    #[test]
+    fn test_part_2_deep() {
+        assert_eq!(part_2([0, u16::MAX, u16::MAX, u16::MAX].into_iter()), 1);
+    }
+}
@@ -1,28 +1,32 @@
-use std::{collections::HashSet, fmt::Display, str::FromStr};
+use std::{fmt::Display, str::FromStr};
 
 use crate::*;
 
 use bitvec::prelude::*;
 
+/// One output pixel for each of the 2^9 possible 3x3 neighborhoods.
+const ALGORITHM_LEN: usize = 512;
+
 #[derive(Clone)]
 pub struct TrenchScan {
     algorithm: BitVec,
-    image: BitVec,
-    x_min: isize,
-    x_max: isize,
-    y_min: isize,
-    y_max: isize,
+    /// The part of the infinite image that isn't all [`oob_is_lit`]. Its
+    /// top-left pixel is at `(0, 0)`.
+    ///
+    /// [`oob_is_lit`]: Self::oob_is_lit
+    image: Grid<bool>,
     oob_is_lit: bool,
 }
 
 impl Display for TrenchScan {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in self.y_min - 2..=self.y_max + 2 {
-            for x in self.x_min - 2..=self.x_max + 2 {
+        let (width, height) = (self.width(), self.height());
+        for y in -2..height + 2 {
+            for x in -2..width + 2 {
                 let c = if self.is_lit(&(x, y)) { '#' } else { '.' };
                 write!(f, "{}", c)?;
             }
-            writeln!(f, "")?;
+            writeln!(f)?;
         }
         Ok(())
     }
@@ -31,24 +35,22 @@ impl Display for TrenchScan {
 impl TrenchScan {
     #[inline]
     fn width(&self) -> isize {
-        self.x_max - self.x_min + 1
+        self.image.width() as isize
     }
 
     #[inline]
     fn height(&self) -> isize {
-        self.y_max - self.y_min + 1
+        self.image.height() as isize
     }
 
     #[inline]
-    fn is_lit(&self, (x, y): &(isize, isize)) -> bool {
-        if *x < self.x_min || *x > self.x_max || *y < self.y_min || *y > self.y_max {
-            // Out of bounds.
-            self.oob_is_lit
-        } else if self.image[((y - self.y_min) * self.width() + x - self.x_min) as usize] {
-            true
-        } else {
-            false
+    fn is_lit(&self, &(x, y): &(isize, isize)) -> bool {
+        match (usize::try_from(x), usize::try_from(y)) {
+            (Ok(x), Ok(y)) => self.image.get((x, y)).copied(),
+            _ => None,
         }
+        // Out of bounds.
+        .unwrap_or(self.oob_is_lit)
     }
 
     #[inline]
@@ -77,69 +79,59 @@ impl TrenchScan {
     }
 
     pub fn step(&mut self) {
-        self.image = {
-            let mut image =
-                BitVec::with_capacity(((self.width() + 4) * (self.height() + 4)) as usize);
-            for y in self.y_min - 2..=self.y_max + 2 {
-                for x in self.x_min - 2..=self.x_max + 2 {
-                    image.push(self.is_lit_next_step((x, y)));
-                }
-            }
-            image
-        };
-        self.x_min -= 2;
-        self.x_max += 2;
-        self.y_min -= 2;
-        self.y_max += 2;
+        // The image grows by 2 pixels on every side, and the new top-left
+        // pixel is at (-2, -2) in the old image.
+        self.image = Grid::from_fn(self.image.width() + 4, self.image.height() + 4, |(x, y)| {
+            self.is_lit_next_step((x as isize - 2, y as isize - 2))
+        });
         self.oob_is_lit = !self.oob_is_lit && self.algorithm[0];
     }
 
     pub fn pixels_lit(&self) -> usize {
-        self.image.count_ones()
+        self.image.iter().filter(|&&lit| lit).count()
     }
 }
 
 impl FromStr for TrenchScan {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-
-        let algorithm_line = lines.next().ok_or_else(|| format!("Missing first line"))?;
-
-        // Skip blank line between algorithm and image.
-        lines.next();
-
-        let is_lit = |c: char| match c {
-            '#' => Ok(true),
-            '.' => Ok(false),
-            _ => Err(format!("Unexpected character in image: {:?}", c)),
-        };
-
-        let mut algorithm = BitVec::with_capacity(algorithm_line.len());
-        for c in algorithm_line.chars() {
-            algorithm.push(is_lit(c)?);
+        let algorithm_line = s
+            .lines()
+            .next()
+            .filter(|line| !line.is_empty())
+            .ok_or_else(|| {
+                Error::new(ErrorKind::UnexpectedEnd {
+                    expected: "an image enhancement algorithm",
+                })
+            })?;
+
+        let algorithm =
+            Grid::parse_pixels(algorithm_line).map_err(|err| err.within(s, algorithm_line))?;
+        if algorithm.len() != ALGORITHM_LEN {
+            return Err(Error::at(
+                s,
+                algorithm_line,
+                ErrorKind::WrongFieldCount {
+                    what: "pixels in the image enhancement algorithm",
+                    expected: ALGORITHM_LEN,
+                    found: algorithm.len(),
+                },
+            ));
         }
 
-        let mut image = BitVec::new();
-        let mut x_max = isize::MIN;
-        let mut y_max = isize::MIN;
-        for (y, line) in lines.enumerate() {
-            let y = y as isize;
-            y_max = y.max(y_max);
-            for (x, c) in line.chars().enumerate() {
-                let x = x as isize;
-                image.push(is_lit(c)?);
-                x_max = x.max(x_max);
-            }
-        }
+        // Skip the blank line between the algorithm and the image.
+        let image = s
+            .split_inclusive('\n')
+            .nth(2)
+            .map_or(&s[s.len()..], |line| {
+                &s[line.as_ptr() as usize - s.as_ptr() as usize..]
+            });
+        let image = Grid::parse_pixels(image).map_err(|err| err.within(s, image))?;
+
         Ok(Self {
-            algorithm,
+            algorithm: algorithm.iter().collect(),
             image,
-            x_min: 0,
-            x_max,
-            y_min: 0,
-            y_max,
             oob_is_lit: false,
         })
     }
@@ -160,6 +152,30 @@ pub fn part_2(mut input: Input) -> usize {
     input.pixels_lit()
 }
 
+pub struct TrenchMap;
+
+impl Solution for TrenchMap {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 20;
+    const NAME: &'static str = "day_20_trench_map";
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part_2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,7 +199,7 @@ mod tests {
     }
 
     fn input() -> Input {
-        input!("day_20_trench_map").parse().unwrap()
+        input!(2021, "day_20_trench_map").parse().unwrap()
     }
 
     #[test]
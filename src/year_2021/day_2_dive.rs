@@ -10,14 +10,17 @@ enum Direction {
 }
 
 impl FromStr for Direction {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "forward" => Ok(Direction::Forward),
             "down" => Ok(Direction::Down),
             "up" => Ok(Direction::Up),
-            _ => Err(format!("Unknown direction {:#?}", s)),
+            _ => Err(Error::new(ErrorKind::UnexpectedToken {
+                found: s.to_owned(),
+                expected: "\"forward\", \"down\", or \"up\"",
+            })),
         }
     }
 }
@@ -29,15 +32,13 @@ pub struct Command {
 }
 
 impl FromStr for Command {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (direction, amount) = s
-            .split_once(' ')
-            .ok_or_else(|| format!("Missing space delimiter in {:#?}", s))?;
+        let (direction, amount) = expect_split(s, s, " ")?;
         Ok(Command {
-            direction: direction.parse()?,
-            amount: amount.parse::<i32>().map_err(|e| e.to_string())?,
+            direction: parse_at(s, direction)?,
+            amount: parse_int(s, amount)?,
         })
     }
 }
@@ -108,6 +109,30 @@ pub fn part_2(commands: impl Iterator<Item = Command>) -> i32 {
         .into()
 }
 
+pub struct Dive;
+
+impl Solution for Dive {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 2;
+    const NAME: &'static str = "day_2_dive";
+
+    type Input = Vec<Command>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_lines(input)
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input.into_iter()))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part_2(input.into_iter()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,7 +170,7 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(input!("day_2_dive", Command)), 2027977);
+        assert_eq!(part_1(input!(2021, "day_2_dive", Command)), 2027977);
     }
 
     #[test]
@@ -155,6 +180,6 @@ mod tests {
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(input!("day_2_dive", Command)), 1903644897);
+        assert_eq!(part_2(input!(2021, "day_2_dive", Command)), 1903644897);
     }
 }
@@ -0,0 +1,182 @@
+use std::str::FromStr;
+
+use crate::*;
+
+/// The diagnostic report: a list of binary numbers, each `bits` wide.
+pub struct DiagnosticReport {
+    numbers: Vec<usize>,
+    bits: usize,
+}
+
+impl FromStr for DiagnosticReport {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_binary = |line: &str| {
+            if line.len() > usize::BITS as usize {
+                return Err(Error::at(s, line, ErrorKind::IntegerOverflow));
+            }
+            line.char_indices().try_fold(0, |num, (i, c)| match c {
+                '0' => Ok(num << 1),
+                '1' => Ok((num << 1) + 1),
+                _ => Err(Error::at(
+                    s,
+                    &line[i..],
+                    ErrorKind::UnexpectedChar {
+                        found: c,
+                        expected: "'0' or '1'",
+                    },
+                )),
+            })
+        };
+        let numbers = s.lines().map(parse_binary).collect::<Result<_, _>>()?;
+        Ok(Self {
+            numbers,
+            bits: s.lines().map(str::len).max().unwrap_or(0),
+        })
+    }
+}
+
+fn ones_per_bit(numbers: impl Iterator<Item = usize>, bits: usize) -> Vec<usize> {
+    let mut ones = vec![0; bits];
+    for num in numbers {
+        for (bit, bit_ones) in ones.iter_mut().enumerate() {
+            *bit_ones += (num >> bit) & 1;
+        }
+    }
+    ones
+}
+
+pub fn power_consumption(numbers: &[usize], bits: usize) -> usize {
+    let ones = ones_per_bit(numbers.iter().copied(), bits);
+    // If there are more than `threshold` 1-bits at a certain position, 1 is the
+    // most common bit at that position.
+    let threshold = numbers.len() / 2;
+    let gamma: usize = ones
+        .into_iter()
+        .enumerate()
+        .fold(0, |acc, (bit, bit_ones)| {
+            let bit_value = if bit_ones >= threshold { 1 } else { 0 };
+            acc + (bit_value << bit)
+        });
+    // 0b1111...1111 for `bits` ones.
+    let all_ones = 1usize.checked_shl(bits as u32).unwrap_or(0).wrapping_sub(1);
+    let epsilon = !gamma & all_ones;
+    gamma * epsilon
+}
+
+pub fn life_support_rating(numbers: &[usize], bits: usize) -> Result<usize, Error> {
+    let mut oxygen_numbers = numbers.to_vec();
+    let mut oxygen_rating = None;
+    for bit in (0..bits).rev() {
+        let ones = ones_per_bit(oxygen_numbers.iter().copied(), bits);
+        // Letting `b = ones[bit]` and `n = oxygen_numbers.len()`;
+        // The zero count is given by `n - b`, so "there are more ones than
+        // zeros" is:
+        //      b ≥ n - b
+        //       ...
+        //     2b ≥ n
+        let most_common_bit = if 2 * ones[bit] >= oxygen_numbers.len() {
+            1
+        } else {
+            0
+        };
+        oxygen_numbers.retain(|num| (num >> bit) & 1 == most_common_bit);
+
+        if oxygen_numbers.len() == 1 {
+            oxygen_rating = Some(oxygen_numbers[0]);
+            break;
+        }
+    }
+
+    let mut co2_numbers = numbers.to_vec();
+    let mut co2_rating = None;
+    for bit in (0..bits).rev() {
+        let ones = ones_per_bit(co2_numbers.iter().copied(), bits);
+        let least_common_bit = if 2 * ones[bit] >= co2_numbers.len() {
+            0
+        } else {
+            1
+        };
+        co2_numbers.retain(|num| (num >> bit) & 1 == least_common_bit);
+
+        if co2_numbers.len() == 1 {
+            co2_rating = Some(co2_numbers[0]);
+            break;
+        }
+    }
+
+    let oxygen_rating =
+        oxygen_rating.ok_or_else(|| Error::no_solution("no oxygen generator rating found"))?;
+    let co2_rating =
+        co2_rating.ok_or_else(|| Error::no_solution("no CO₂ scrubber rating found"))?;
+    Ok(oxygen_rating * co2_rating)
+}
+
+pub fn part_1<I: Iterator<Item = usize>, const BITS: usize>(numbers: I) -> usize {
+    power_consumption(&numbers.collect::<Vec<_>>(), BITS)
+}
+
+pub fn part_2<I: Iterator<Item = usize>, const BITS: usize>(numbers: I) -> Result<usize, Error> {
+    life_support_rating(&numbers.collect::<Vec<_>>(), BITS)
+}
+
+pub struct BinaryDiagnostic;
+
+impl Solution for BinaryDiagnostic {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 3;
+    const NAME: &'static str = "day_3_binary_diagnostic";
+
+    type Input = DiagnosticReport;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(power_consumption(&input.numbers, input.bits))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        life_support_rating(&input.numbers, input.bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const SAMPLE: [usize; 12] = [
+        0b00100, 0b11110, 0b10110, 0b10111, 0b10101, 0b01111, 0b00111, 0b11100, 0b10000, 0b11001,
+        0b00010, 0b01010,
+    ];
+
+    fn input() -> impl Iterator<Item = usize> {
+        BinaryDiagnostic::parse(&input!(2021, "day_3_binary_diagnostic"))
+            .unwrap()
+            .numbers
+            .into_iter()
+    }
+
+    #[test]
+    fn test_part_1_sample() {
+        assert_eq!(part_1::<_, 5>(SAMPLE.into_iter()), 198);
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1::<_, 12>(input()), 3985686);
+    }
+
+    #[test]
+    fn test_part_2_sample() {
+        assert_eq!(part_2::<_, 5>(SAMPLE.into_iter()), Ok(230));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2::<_, 12>(input()), Ok(2555739));
+    }
+}
@@ -9,20 +9,19 @@ pub struct BingoGame {
 }
 
 impl FromStr for BingoGame {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut chunks = s.split("\n\n");
         let numbers = chunks
             .next()
-            .ok_or_else(|| "Expected first line".to_owned())?
+            .unwrap_or_default()
             .split(',')
-            .map(|num| num.parse())
-            .collect::<Result<Vec<usize>, std::num::ParseIntError>>()
-            .map_err(|err| err.to_string())?;
+            .map(|num| parse_int(s, num))
+            .collect::<Result<Vec<usize>, _>>()?;
 
         let boards = chunks
-            .map(|board| board.parse())
+            .map(|board| parse_at(s, board))
             .collect::<Result<Vec<Board>, _>>()?;
 
         Ok(Self { numbers, boards })
@@ -33,18 +32,24 @@ impl FromStr for BingoGame {
 pub struct Board([(usize, bool); 25]);
 
 impl FromStr for Board {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(board: &str) -> Result<Self, Self::Err> {
         let mut nums: Vec<(usize, bool)> = Vec::with_capacity(25);
         for line in board.lines() {
             for num in line.split_ascii_whitespace() {
-                nums.push((num.parse::<usize>().map_err(|err| err.to_string())?, false));
+                nums.push((parse_int(board, num)?, false));
             }
         }
 
         Ok(Board(nums.try_conv::<[(usize, bool); 25]>().map_err(
-            |err| format!("Board had {} numbers instead of 25", err.len()),
+            |err| {
+                Error::new(ErrorKind::WrongFieldCount {
+                    what: "numbers on the board",
+                    expected: 25,
+                    found: err.len(),
+                })
+            },
         )?))
     }
 }
@@ -71,12 +76,10 @@ impl Board {
     }
 
     pub fn is_winning(&self) -> bool {
-        (0..5)
-            .map(|idx| {
-                self.row(idx).into_iter().all(|(_, marked)| *marked)
-                    || self.col(idx).into_iter().all(|(_, marked)| marked)
-            })
-            .any(|all_marked| all_marked)
+        (0..5).any(|idx| {
+            self.row(idx).iter().all(|(_, marked)| *marked)
+                || self.col(idx).into_iter().all(|(_, marked)| marked)
+        })
     }
 
     pub fn unmarked_sum(&self) -> usize {
@@ -99,30 +102,54 @@ impl Board {
     }
 }
 
-pub fn part_1(mut game: BingoGame) -> usize {
+pub fn part_1(mut game: BingoGame) -> Result<usize, Error> {
     for number in game.numbers {
         for board in &mut game.boards {
             board.mark(number);
             if board.is_winning() {
-                return number * board.unmarked_sum();
+                return Ok(number * board.unmarked_sum());
             }
         }
     }
-    panic!("No winning board found");
+    Err(Error::no_solution("no board ever wins"))
 }
 
-pub fn part_2(mut game: BingoGame) -> usize {
+pub fn part_2(mut game: BingoGame) -> Result<usize, Error> {
     for number in game.numbers {
         for board in &mut game.boards {
             board.mark(number);
         }
         if game.boards.len() > 1 {
             game.boards.retain(|board| !board.is_winning());
-        } else if game.boards[0].is_winning() {
-            return number * game.boards[0].unmarked_sum();
+        } else if let Some(board) = game.boards.first().filter(|board| board.is_winning()) {
+            return Ok(number * board.unmarked_sum());
         }
     }
-    panic!("No last winner");
+    Err(Error::no_solution("no single board wins last"))
+}
+
+pub struct GiantSquid;
+
+impl Solution for GiantSquid {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 4;
+    const NAME: &'static str = "day_4_giant_squid";
+
+    type Input = BingoGame;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        part_2(input)
+    }
 }
 
 #[cfg(test)]
@@ -130,7 +157,7 @@ mod tests {
     use super::*;
 
     fn input() -> BingoGame {
-        input!("day_4_giant_squid").parse().unwrap()
+        input!(2021, "day_4_giant_squid").parse().unwrap()
     }
 
     fn sample() -> BingoGame {
@@ -160,21 +187,21 @@ mod tests {
 
     #[test]
     fn test_part_1_sample() {
-        assert_eq!(part_1(sample()), 4512);
+        assert_eq!(part_1(sample()), Ok(4512));
     }
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(input()), 41668);
+        assert_eq!(part_1(input()), Ok(41668));
     }
 
     #[test]
     fn test_part_2_sample() {
-        assert_eq!(part_2(sample()), 1924);
+        assert_eq!(part_2(sample()), Ok(1924));
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(input()), 10478);
+        assert_eq!(part_2(input()), Ok(10478));
     }
 }
@@ -46,36 +46,23 @@ impl LineSegment {
                 .zip(std::iter::repeat(self.y1))
                 .collect()
         } else {
-            self.x_values()
-                .into_iter()
-                .zip(self.y_values().into_iter())
-                .collect()
+            self.x_values().into_iter().zip(self.y_values()).collect()
         }
     }
 }
 
 impl FromStr for LineSegment {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start, end) = s
-            .split_once(" -> ")
-            .ok_or_else(|| "Segment must contain ' -> '")?;
-        let (x1, y1) = start
-            .split_once(',')
-            .ok_or_else(|| "Coordinates must be delimited by ','")?;
-        let (x2, y2) = end
-            .split_once(',')
-            .ok_or_else(|| "Coordinates must be delimited by ','")?;
-        let from_str = |s: &str| {
-            s.parse::<usize>()
-                .map_err(|err| format!("{}: {:?}", err, s))
-        };
+        let (start, end) = expect_split(s, s, " -> ")?;
+        let (x1, y1) = expect_split(s, start, ",")?;
+        let (x2, y2) = expect_split(s, end, ",")?;
         Ok(LineSegment {
-            x1: from_str(x1)?,
-            y1: from_str(y1)?,
-            x2: from_str(x2)?,
-            y2: from_str(y2)?,
+            x1: parse_int(s, x1)?,
+            y1: parse_int(s, y1)?,
+            x2: parse_int(s, x2)?,
+            y2: parse_int(s, y2)?,
         })
     }
 }
@@ -104,6 +91,30 @@ pub fn part_2(segments: impl Iterator<Item = LineSegment>) -> usize {
     get_overlapping_points(segments)
 }
 
+pub struct HydrothermalVenture;
+
+impl Solution for HydrothermalVenture {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 5;
+    const NAME: &'static str = "day_5_hydrothermal_venture";
+
+    type Input = Vec<LineSegment>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_lines(input)
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input.into_iter()))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part_2(input.into_iter()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,7 +144,7 @@ mod tests {
     #[test]
     fn test_part_1() {
         assert_eq!(
-            part_1(input!("day_5_hydrothermal_venture", LineSegment)),
+            part_1(input!(2021, "day_5_hydrothermal_venture", LineSegment)),
             7269
         );
     }
@@ -146,7 +157,7 @@ mod tests {
     #[test]
     fn test_part_2() {
         assert_eq!(
-            part_2(input!("day_5_hydrothermal_venture", LineSegment)),
+            part_2(input!(2021, "day_5_hydrothermal_venture", LineSegment)),
             21140
         );
     }
@@ -19,6 +19,12 @@ impl FromIterator<usize> for School {
     }
 }
 
+impl Default for School {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl School {
     pub fn new() -> Self {
         Self([0; MAX_LANTERNFISH_TIMER + 1])
@@ -49,6 +55,35 @@ pub fn part_2(mut school: School) -> usize {
     school.total_fish()
 }
 
+pub struct Lanternfish;
+
+impl Solution for Lanternfish {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 6;
+    const NAME: &'static str = "day_6_lanternfish";
+
+    type Input = School;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let timers: Vec<usize> = parse_comma_separated(input)?;
+        if let Some(i) = timers.iter().position(|time| *time > MAX_LANTERNFISH_TIMER) {
+            let timer = input.split(',').nth(i).unwrap_or(input);
+            return Err(Error::at(input, timer, ErrorKind::IntegerOverflow));
+        }
+        Ok(timers.into_iter().collect())
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part_2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,7 +93,7 @@ mod tests {
     }
 
     fn input() -> School {
-        input!("day_6_lanternfish")
+        input!(2021, "day_6_lanternfish")
             .trim()
             .split(',')
             .map(|time| {
@@ -0,0 +1,138 @@
+use crate::*;
+
+fn abs_diff(a: usize, b: usize) -> usize {
+    a.abs_diff(b)
+}
+
+// See: https://cseweb.ucsd.edu/groups/tatami/handdemos/sum/
+fn sum_of_first_n(n: usize) -> Option<usize> {
+    Some(n.checked_mul(n.checked_add(1)?)? / 2)
+}
+
+// Order doesn't matter so we can sort the data.
+fn median(data: &mut [usize]) -> usize {
+    data.sort();
+    data[data.len() / 2]
+}
+
+/// The total cost of moving every crab, or `None` if it overflows.
+fn alignment_cost(positions: &[usize], metric: impl Fn(usize) -> Option<usize>) -> Option<usize> {
+    positions.iter().try_fold(0usize, |total, position| {
+        total.checked_add(metric(*position)?)
+    })
+}
+
+fn linear_alignment_cost(positions: &[usize], align_at: usize) -> Option<usize> {
+    alignment_cost(positions, |position| Some(abs_diff(position, align_at)))
+}
+
+fn increasing_alignment_cost(positions: &[usize], align_at: usize) -> Option<usize> {
+    alignment_cost(positions, |position| {
+        sum_of_first_n(abs_diff(position, align_at))
+    })
+}
+
+pub fn part_1(mut positions: Vec<usize>) -> Result<usize, Error> {
+    let guess = median(&mut positions);
+    linear_alignment_cost(&positions, guess).ok_or_else(Error::overflow)
+}
+
+pub fn part_2(mut positions: Vec<usize>) -> Result<usize, Error> {
+    let initial_guess = median(&mut positions);
+    let mut best_cost =
+        increasing_alignment_cost(&positions, initial_guess).ok_or_else(Error::overflow)?;
+    // If we start seeing costs 2x the initial guess, we're probably done:
+    let bailout_cost = best_cost.saturating_mul(2);
+
+    // If our initial guess is `n`, our guesses are `n + 1, n - 1, n + 2, n - 2, ...`
+    let guesses = (0..).flat_map(|i| {
+        [initial_guess.checked_add(i), initial_guess.checked_sub(i)]
+            .into_iter()
+            .flatten()
+    });
+    for guess in guesses {
+        // A cost too big to count is certainly past the bailout cost.
+        let cost = match increasing_alignment_cost(&positions, guess) {
+            Some(cost) => cost,
+            None => break,
+        };
+        best_cost = best_cost.min(cost);
+        if cost > bailout_cost {
+            break;
+        }
+    }
+    Ok(best_cost)
+}
+
+pub struct TheTreacheryOfWhales;
+
+impl Solution for TheTreacheryOfWhales {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 7;
+    const NAME: &'static str = "day_7_the_treachery_of_whales";
+
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_comma_separated(input)
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<usize> {
+        [16, 1, 2, 0, 4, 2, 7, 1, 2, 14].into()
+    }
+
+    fn input() -> Vec<usize> {
+        input!(2021, "day_7_the_treachery_of_whales")
+            .trim()
+            .split(',')
+            .map(|num| num.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_part_1_sample() {
+        assert_eq!(part_1(sample()), Ok(37));
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(input()), Ok(341558));
+    }
+
+    #[test]
+    fn test_part_2_sample() {
+        assert_eq!(part_2(sample()), Ok(168));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(input()), Ok(93214037));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let far = vec![0, 1 << 40, 1 << 40];
+        assert_eq!(part_1(far.clone()), Ok(1 << 40));
+        assert_eq!(part_2(far), Err(Error::overflow()));
+        assert_eq!(part_1(vec![0, usize::MAX, usize::MAX]), Ok(usize::MAX));
+        assert_eq!(
+            part_1(vec![0, 0, usize::MAX, usize::MAX]),
+            Err(Error::overflow())
+        );
+    }
+}
@@ -22,7 +22,8 @@ impl Display {
         segment_counts
     }
 
-    fn decode(&self) -> HashMap<Segment, Segment> {
+    fn decode(&self) -> Result<HashMap<Segment, Segment>, Error> {
+        let miswired = || Error::no_solution(MISWIRED);
         let segment_counts = self.segment_counts();
 
         // If a mapping `i -> o` is in `inv_swaps`, it means that when segment
@@ -49,18 +50,24 @@ impl Display {
                 9 => {
                     inv_swaps.insert(Segment::F, *segment);
                 }
-                _ => {
-                    unreachable!();
-                }
+                _ => return Err(miswired()),
             }
         }
 
-        let digit_one = self.digits.iter().find(|digit| digit.len() == 2).unwrap();
-        let digit_four = self.digits.iter().find(|digit| digit.len() == 4).unwrap();
+        let digit_one = self
+            .digits
+            .iter()
+            .find(|digit| digit.len() == 2)
+            .ok_or_else(miswired)?;
+        let digit_four = self
+            .digits
+            .iter()
+            .find(|digit| digit.len() == 4)
+            .ok_or_else(miswired)?;
 
         // The 2 segments in `digit` are C and F.
         // We know F; C is the other one.
-        let f = *inv_swaps.get(&Segment::F).unwrap();
+        let f = *inv_swaps.get(&Segment::F).ok_or_else(miswired)?;
 
         for segment in digit_one {
             if *segment != f {
@@ -80,8 +87,8 @@ impl Display {
 
         // The 4 segments in `digit` are B, C, D, and F.
         // We know B, C, and F; D is the other one.
-        let b = *inv_swaps.get(&Segment::B).unwrap();
-        let c = *inv_swaps.get(&Segment::C).unwrap();
+        let b = *inv_swaps.get(&Segment::B).ok_or_else(miswired)?;
+        let c = *inv_swaps.get(&Segment::C).ok_or_else(miswired)?;
 
         for segment in digit_four {
             if *segment != b && *segment != c && *segment != f {
@@ -99,66 +106,84 @@ impl Display {
             }
         }
 
-        inv_swaps.into_iter().map(|(o, i)| (i, o)).collect()
+        Ok(inv_swaps.into_iter().map(|(o, i)| (i, o)).collect())
     }
 
-    pub fn output_value(&self) -> usize {
-        let decode_map = self.decode();
-        1000 * decode_digit(&decode_map, &self.output[0])
-            + 100 * decode_digit(&decode_map, &self.output[1])
-            + 10 * decode_digit(&decode_map, &self.output[2])
-            + decode_digit(&decode_map, &self.output[3])
+    pub fn output_value(&self) -> Result<usize, Error> {
+        let decode_map = self.decode()?;
+        Ok(1000 * decode_digit(&decode_map, &self.output[0])?
+            + 100 * decode_digit(&decode_map, &self.output[1])?
+            + 10 * decode_digit(&decode_map, &self.output[2])?
+            + decode_digit(&decode_map, &self.output[3])?)
     }
 }
 
-fn decode_digit(map: &HashMap<Segment, Segment>, digit: &HashSet<Segment>) -> usize {
+const MISWIRED: &str = "the signal patterns don't match any wiring of the display";
+
+fn decode_digit(map: &HashMap<Segment, Segment>, digit: &HashSet<Segment>) -> Result<usize, Error> {
     let decoded_segments: Vec<_> = digit
+        .iter()
+        .map(|segment| map.get(segment).ok_or_else(|| Error::no_solution(MISWIRED)))
+        .collect::<Result<Vec<_>, _>>()?
         .into_iter()
-        .map(|segment| map.get(&segment).unwrap())
         .sorted()
         .collect();
     use Segment::*;
-    match &decoded_segments[..] {
-        &[A, B, C, E, F, G] => 0,
-        &[C, F] => 1,
-        &[A, C, D, E, G] => 2,
-        &[A, C, D, F, G] => 3,
-        &[B, C, D, F] => 4,
-        &[A, B, D, F, G] => 5,
-        &[A, B, D, E, F, G] => 6,
-        &[A, C, F] => 7,
-        &[A, B, C, D, E, F, G] => 8,
-        &[A, B, C, D, F, G] => 9,
-        _ => unreachable!(),
-    }
+    Ok(match decoded_segments[..] {
+        [A, B, C, E, F, G] => 0,
+        [C, F] => 1,
+        [A, C, D, E, G] => 2,
+        [A, C, D, F, G] => 3,
+        [B, C, D, F] => 4,
+        [A, B, D, F, G] => 5,
+        [A, B, D, E, F, G] => 6,
+        [A, C, F] => 7,
+        [A, B, C, D, E, F, G] => 8,
+        [A, B, C, D, F, G] => 9,
+        _ => return Err(Error::no_solution(MISWIRED)),
+    })
 }
 
 impl FromStr for Display {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (signals, output) = s
-            .split_once(" | ")
-            .ok_or_else(|| format!("No '|' in display: {:?}", s))?;
-        Ok(Self {
-            digits: signals
+        let (signals, output) = expect_split(s, s, " | ")?;
+        let parse_digits = |digits: &str, expected: usize| {
+            let parsed = digits
                 .split_ascii_whitespace()
-                .map(parse_segments)
-                .collect::<Result<Vec<_>, _>>()?
-                .try_into()
-                .map_err(|err: Vec<_>| format!("Expected 10 digits, got {}", err.len()))?,
-            output: output
-                .split_ascii_whitespace()
-                .map(parse_segments)
-                .collect::<Result<Vec<_>, _>>()?
-                .try_into()
-                .map_err(|err: Vec<_>| format!("Expected 4 digits, got {}", err.len()))?,
+                .map(|segments| parse_segments(s, segments))
+                .collect::<Result<Vec<_>, _>>()?;
+            if parsed.len() == expected {
+                Ok(parsed)
+            } else {
+                Err(Error::at(
+                    s,
+                    digits,
+                    ErrorKind::WrongFieldCount {
+                        what: "digits",
+                        expected,
+                        found: parsed.len(),
+                    },
+                ))
+            }
+        };
+        Ok(Self {
+            digits: parse_digits(signals, 10)?.try_into().unwrap(),
+            output: parse_digits(output, 4)?.try_into().unwrap(),
         })
     }
 }
 
-fn parse_segments(segments: &str) -> Result<HashSet<Segment>, String> {
-    segments.chars().map(|segment| segment.try_into()).collect()
+fn parse_segments(source: &str, segments: &str) -> Result<HashSet<Segment>, Error> {
+    segments
+        .char_indices()
+        .map(|(i, segment)| {
+            segment
+                .try_into()
+                .map_err(|kind| Error::at(source, &segments[i..], kind))
+        })
+        .collect()
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -173,7 +198,7 @@ pub enum Segment {
 }
 
 impl TryFrom<char> for Segment {
-    type Error = String;
+    type Error = ErrorKind;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
@@ -184,10 +209,10 @@ impl TryFrom<char> for Segment {
             'e' => Ok(Segment::E),
             'f' => Ok(Segment::F),
             'g' => Ok(Segment::G),
-            _ => Err(format!(
-                "Unknown segment {}; expected one of 'a'-'f'",
-                value
-            )),
+            _ => Err(ErrorKind::UnexpectedChar {
+                found: value,
+                expected: "a segment from 'a' to 'g'",
+            }),
         }
     }
 }
@@ -205,8 +230,46 @@ pub fn part_1(displays: impl Iterator<Item = Display>) -> usize {
         .count()
 }
 
-pub fn part_2(displays: impl Iterator<Item = Display>) -> usize {
-    displays.map(|display| display.output_value()).sum()
+pub fn part_2(displays: impl Iterator<Item = Display>) -> Result<usize, Error> {
+    displays
+        .enumerate()
+        .map(|(i, display)| {
+            display.output_value().map_err(|err| {
+                // Point at the display that couldn't be decoded.
+                Error::at_position(
+                    Position {
+                        line: i + 1,
+                        column: 1,
+                    },
+                    err.kind().clone(),
+                )
+            })
+        })
+        .sum()
+}
+
+pub struct SevenSegmentSearch;
+
+impl Solution for SevenSegmentSearch {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 8;
+    const NAME: &'static str = "day_8_seven_segment_search";
+
+    type Input = Vec<Display>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_lines(input)
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input.into_iter()))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        part_2(input.into_iter())
+    }
 }
 
 #[cfg(test)]
@@ -229,7 +292,7 @@ mod tests {
     }
 
     fn input() -> impl Iterator<Item = Display> {
-        input!("day_8_seven_segment_search", Display)
+        input!(2021, "day_8_seven_segment_search", Display)
     }
 
     #[test]
@@ -244,11 +307,24 @@ mod tests {
 
     #[test]
     fn test_part_2_sample() {
-        assert_eq!(part_2(sample()), 61229);
+        assert_eq!(part_2(sample()), Ok(61229));
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(input()), 998900);
+        assert_eq!(part_2(input()), Ok(998900));
+    }
+
+    #[test]
+    fn test_part_2_miswired() {
+        // Two copies of the digit 1, and no 7.
+        let display = "ab ab abcdefg abcdf gcdfa fbcad eafb cagedb cefabd cdfgeb | ab ab ab ab";
+        assert_eq!(
+            part_2(std::iter::once(display.parse().unwrap()))
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 1: no solution: \
+             the signal patterns don't match any wiring of the display"
+        );
     }
 }
@@ -0,0 +1,727 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::*;
+
+/// Locations this high aren't part of any basin, unless
+/// [`HeightMap::with_wall`] says otherwise.
+const WALL: u8 = 9;
+
+pub struct HeightMap {
+    heights: Grid<u8>,
+    /// Locations at least this high are walls between basins.
+    wall: u8,
+}
+
+impl FromStr for HeightMap {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_digits(s).map(|heights| Self {
+            heights,
+            wall: WALL,
+        })
+    }
+}
+
+impl HeightMap {
+    /// Treat locations at least `wall` high as the walls between basins,
+    /// instead of only the 9s.
+    pub fn with_wall(self, wall: u8) -> Self {
+        Self { wall, ..self }
+    }
+
+    pub fn wall(&self) -> u8 {
+        self.wall
+    }
+
+    fn is_wall(&self, point: Point) -> bool {
+        self.heights[point] >= self.wall
+    }
+
+    pub fn neighbors(&self, point: Point) -> impl Iterator<Item = u8> + '_ {
+        self.heights
+            .neighbors_4(point)
+            .map(|neighbor| self.heights[neighbor])
+    }
+
+    /// Whether every neighbor is strictly higher. The lowest location on a
+    /// flat-bottomed basin isn't a low point, but see [`Self::low_regions`].
+    pub fn is_low_point(&self, point: Point) -> bool {
+        let center_height = self.heights[point];
+        self.neighbors(point).all(|height| center_height < height)
+    }
+
+    pub fn risk_level(&self, point: Point) -> u8 {
+        self.heights[point] + 1
+    }
+
+    pub fn coords(&self) -> impl Iterator<Item = Point> {
+        self.heights.points()
+    }
+
+    /// The size of the basin that flows down to `point`, which can be any
+    /// location in a [`LowRegion`], tied or not.
+    pub fn basin_size(&self, point: Point) -> usize {
+        // Breadth-first search to increasing neighbors.
+        let mut to_explore = VecDeque::with_capacity(self.heights.height());
+        to_explore.push_back(point);
+        let mut explored = HashSet::with_capacity(self.heights.height());
+        explored.insert(point);
+        let mut basin_size = 0;
+        while let Some(current) = to_explore.pop_front() {
+            basin_size += 1;
+            for neighbor in self.heights.neighbors_4(current) {
+                if !explored.contains(&neighbor)
+                    && !self.is_wall(neighbor)
+                    && self.heights[neighbor] >= self.heights[current]
+                {
+                    // Neighbor is in the basin:
+                    explored.insert(neighbor);
+                    to_explore.push_back(neighbor);
+                }
+            }
+        }
+        basin_size
+    }
+
+    /// Every flat area with nothing lower next to it, in the order their
+    /// first location comes in the map. Each low point is one on its own, and
+    /// the locations tied for lowest at the bottom of a flat basin are one
+    /// together. Walls are never low regions.
+    pub fn low_regions(&self) -> Vec<LowRegion> {
+        let mut explored = Grid::new(self.heights.width(), self.heights.height(), false);
+        let mut regions = Vec::new();
+        for start in self.heights.points() {
+            if self.is_wall(start) || explored[start] {
+                continue;
+            }
+
+            // Flood fill everything at the same height.
+            let height = self.heights[start];
+            let mut cells = vec![start];
+            explored[start] = true;
+            let mut is_low = true;
+            let mut i = 0;
+            while let Some(&current) = cells.get(i) {
+                for neighbor in self.heights.neighbors_4(current) {
+                    let neighbor_height = self.heights[neighbor];
+                    if neighbor_height < height {
+                        is_low = false;
+                    } else if neighbor_height == height && !explored[neighbor] {
+                        explored[neighbor] = true;
+                        cells.push(neighbor);
+                    }
+                }
+                i += 1;
+            }
+
+            if is_low {
+                regions.push(LowRegion { height, cells });
+            }
+        }
+        regions
+    }
+
+    /// Label every location with the basin it's in, in one pass. Basins are
+    /// the areas separated by walls, numbered in the order their first
+    /// location comes in the map.
+    pub fn basins(&self) -> Basins {
+        let mut labels = Grid::new(self.heights.width(), self.heights.height(), None);
+        let mut basins = Vec::new();
+        for start in self.heights.points() {
+            if self.is_wall(start) || labels[start].is_some() {
+                continue;
+            }
+
+            // Flood fill everything reachable without crossing a wall.
+            let id = basins.len();
+            let mut cells = vec![start];
+            labels[start] = Some(id);
+            let mut i = 0;
+            while let Some(&current) = cells.get(i) {
+                for neighbor in self.heights.neighbors_4(current) {
+                    if !self.is_wall(neighbor) && labels[neighbor].is_none() {
+                        labels[neighbor] = Some(id);
+                        cells.push(neighbor);
+                    }
+                }
+                i += 1;
+            }
+
+            let lowest = cells
+                .iter()
+                .map(|&point| self.heights[point])
+                .min()
+                .unwrap();
+            let low_points = cells
+                .iter()
+                .copied()
+                .filter(|&point| self.heights[point] == lowest)
+                .collect::<Vec<_>>();
+            basins.push(Basin {
+                low_point: low_points[0],
+                low_points,
+                cells,
+            });
+        }
+        Basins { labels, basins }
+    }
+
+    /// Rain `amount` units of water on every location, once a step for
+    /// `steps` steps, and let each unit run downhill until it settles.
+    ///
+    /// Water flows over the terrain and any water that's already settled, to
+    /// the lowest neighbor that's lower than where it is. Where nothing is
+    /// lower, it looks across the flat surface around it for a way down, and
+    /// settles where it is if there's none. Anything that reaches the edge of
+    /// the map runs off it, so basins fill up to their spill height and then
+    /// overflow into their neighbors or off the map.
+    pub fn rain(&self, amount: u32, steps: usize) -> Rainfall {
+        let mut flow = Flow {
+            heights: &self.heights,
+            depths: Grid::new(self.heights.width(), self.heights.height(), 0),
+        };
+        // Where water would settle on the dry map, for telling overflows apart.
+        let drains_to = Grid::from_fn(self.heights.width(), self.heights.height(), |point| {
+            flow.run(point).1.map(|rest| flow.low_point(rest))
+        });
+
+        let mut rainfall = Rainfall {
+            levels: Vec::with_capacity(steps),
+            overflows: Vec::new(),
+            drained: 0,
+        };
+        for step in 1..=steps {
+            for point in self.heights.points() {
+                for _ in 0..amount {
+                    let (path, rest) = flow.run(point);
+                    match rest {
+                        Some(rest) => flow.depths[rest] += 1,
+                        None => rainfall.drained += 1,
+                    }
+
+                    let (from, to) = (drains_to[point], rest.and_then(|rest| drains_to[rest]));
+                    if from != to
+                        && !rainfall
+                            .overflows
+                            .iter()
+                            .any(|overflow| (overflow.from, overflow.to) == (from, to))
+                    {
+                        rainfall.overflows.push(Overflow {
+                            step,
+                            from,
+                            to,
+                            path,
+                        });
+                    }
+                }
+            }
+            rainfall.levels.push(flow.depths.clone());
+        }
+        rainfall
+    }
+}
+
+/// Water settled on a [`HeightMap`].
+struct Flow<'a> {
+    heights: &'a Grid<u8>,
+    depths: Grid<u32>,
+}
+
+impl Flow<'_> {
+    /// The height of the water's surface, or of the ground where it's dry.
+    fn level(&self, point: Point) -> u32 {
+        u32::from(self.heights[point]) + self.depths[point]
+    }
+
+    fn is_edge(&self, (x, y): Point) -> bool {
+        x == 0 || y == 0 || x == self.heights.width() - 1 || y == self.heights.height() - 1
+    }
+
+    /// Where a unit of water falling on `start` runs, and where it settles, or
+    /// `None` if it runs off the map.
+    fn run(&self, start: Point) -> (Vec<Point>, Option<Point>) {
+        let mut path = vec![start];
+        loop {
+            let current = *path.last().unwrap();
+            if self.is_edge(current) {
+                return (path, None);
+            }
+
+            let level = self.level(current);
+            let lowest = self
+                .heights
+                .neighbors_4(current)
+                .min_by_key(|&neighbor| self.level(neighbor))
+                .unwrap();
+            if self.level(lowest) < level {
+                path.push(lowest);
+                continue;
+            }
+
+            match self.way_down(current) {
+                Some(route) => path.extend(route),
+                None => return (path, Some(current)),
+            }
+        }
+    }
+
+    /// The shortest route across the flat surface around `start` to somewhere
+    /// that's next to something lower or on the edge of the map, not
+    /// including `start` itself.
+    fn way_down(&self, start: Point) -> Option<Vec<Point>> {
+        let level = self.level(start);
+        let mut previous = HashMap::from([(start, start)]);
+        let mut to_explore = VecDeque::from([start]);
+        while let Some(current) = to_explore.pop_front() {
+            if current != start
+                && (self.is_edge(current)
+                    || self
+                        .heights
+                        .neighbors_4(current)
+                        .any(|neighbor| self.level(neighbor) < level))
+            {
+                let mut route = vec![current];
+                while previous[route.last().unwrap()] != start {
+                    route.push(previous[route.last().unwrap()]);
+                }
+                route.reverse();
+                return Some(route);
+            }
+
+            for neighbor in self.heights.neighbors_4(current) {
+                if self.level(neighbor) == level && !previous.contains_key(&neighbor) {
+                    previous.insert(neighbor, current);
+                    to_explore.push_back(neighbor);
+                }
+            }
+        }
+        None
+    }
+
+    /// The first location, in map order, of the flat surface around `point`,
+    /// so every location water can settle at in one hollow gets the same one.
+    fn low_point(&self, point: Point) -> Point {
+        let level = self.level(point);
+        let mut explored = HashSet::from([point]);
+        let mut to_explore = vec![point];
+        while let Some(current) = to_explore.pop() {
+            for neighbor in self.heights.neighbors_4(current) {
+                if self.level(neighbor) == level && explored.insert(neighbor) {
+                    to_explore.push(neighbor);
+                }
+            }
+        }
+        explored.into_iter().min_by_key(|&(x, y)| (y, x)).unwrap()
+    }
+}
+
+/// What happened to the water from [`HeightMap::rain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rainfall {
+    /// The depth of water on every location after each step.
+    pub levels: Vec<Grid<u32>>,
+    /// The first time water overflowed between each pair of basins.
+    pub overflows: Vec<Overflow>,
+    /// How much water ran off the edge of the map.
+    pub drained: u64,
+}
+
+impl Rainfall {
+    /// How much water is pooled on the map after the last step.
+    pub fn pooled(&self) -> u64 {
+        self.levels.last().map_or(0, |depths| {
+            depths.iter().map(|&depth| u64::from(depth)).sum()
+        })
+    }
+}
+
+/// Water that settled somewhere other than where it would have on the dry
+/// map, because the basin it fell in was full.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Overflow {
+    /// The step it happened in, counting from 1.
+    pub step: usize,
+    /// The low point of the basin the water fell in, or `None` if it fell
+    /// where it would have run off the map.
+    pub from: Option<Point>,
+    /// The low point of the basin it settled in, or `None` if it ran off the
+    /// map.
+    pub to: Option<Point>,
+    /// Every location it ran through, from where it fell to where it settled
+    /// or ran off the map.
+    pub path: Vec<Point>,
+}
+
+/// A flat area of the map with nothing lower next to it, from
+/// [`HeightMap::low_regions`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LowRegion {
+    pub height: u8,
+    /// Every location, in the order the flood fill reached them.
+    pub cells: Vec<Point>,
+}
+
+impl LowRegion {
+    /// Whether more than one location is tied for the lowest here.
+    pub fn is_tie(&self) -> bool {
+        self.cells.len() > 1
+    }
+}
+
+/// An area of the map that smoke flows down into, from [`HeightMap::basins`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Basin {
+    /// The lowest location, or the first of them if there's a tie.
+    pub low_point: Point,
+    /// Every location tied for the lowest, in the order the flood fill
+    /// reached them.
+    pub low_points: Vec<Point>,
+    /// Every location, in the order the flood fill reached them.
+    pub cells: Vec<Point>,
+}
+
+impl Basin {
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+/// Every basin in a [`HeightMap`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Basins {
+    /// The index in `basins` of each location's basin, or `None` for walls.
+    pub labels: Grid<Option<usize>>,
+    pub basins: Vec<Basin>,
+}
+
+impl Basins {
+    /// The walls next to more than one basin, and the basins on either side
+    /// of each, in order.
+    pub fn boundaries(&self) -> Vec<(Point, Vec<usize>)> {
+        self.labels
+            .enumerate()
+            .filter(|(_, label)| label.is_none())
+            .filter_map(|(point, _)| {
+                let ids = self
+                    .labels
+                    .neighbors_4(point)
+                    .filter_map(|neighbor| self.labels[neighbor])
+                    .sorted()
+                    .dedup()
+                    .collect::<Vec<_>>();
+                (ids.len() > 1).then_some((point, ids))
+            })
+            .collect()
+    }
+}
+
+/// Each basin's locations are drawn with a letter, cycling through `a` to `z`
+/// in basin order, and its low point with the capital letter. Walls between
+/// basins are `+`, and other walls are `#`.
+impl Display for Basins {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let boundaries = self
+            .boundaries()
+            .into_iter()
+            .map(|(point, _)| point)
+            .collect::<HashSet<_>>();
+        for (point, label) in self.labels.enumerate() {
+            let c = match label {
+                Some(id) => {
+                    let c = char::from(b'a' + (id % 26) as u8);
+                    if self.basins[*id].low_point == point {
+                        c.to_ascii_uppercase()
+                    } else {
+                        c
+                    }
+                }
+                None if boundaries.contains(&point) => '+',
+                None => '#',
+            };
+            write!(f, "{}", c)?;
+            if point.0 == self.labels.width() - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn part_1(heights: HeightMap) -> usize {
+    heights
+        .coords()
+        .map(|point| {
+            if heights.is_low_point(point) {
+                heights.risk_level(point) as usize
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+pub fn part_2(heights: HeightMap) -> usize {
+    heights
+        .basins()
+        .basins
+        .iter()
+        .map(Basin::size)
+        .sorted()
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .take(3)
+        .product()
+}
+
+pub struct SmokeBasin;
+
+impl Solution for SmokeBasin {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 9;
+    const NAME: &'static str = "day_9_smoke_basin";
+
+    type Input = HeightMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part_1(input: Self::Input) -> Result<Self::Answer1, Error> {
+        Ok(part_1(input))
+    }
+
+    fn part_2(input: Self::Input) -> Result<Self::Answer2, Error> {
+        Ok(part_2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> HeightMap {
+        "2199943210\n\
+         3987894921\n\
+         9856789892\n\
+         8767896789\n\
+         9899965678\n"
+            .parse()
+            .unwrap()
+    }
+
+    fn input() -> HeightMap {
+        input!(2021, "day_9_smoke_basin").parse().unwrap()
+    }
+
+    #[test]
+    fn test_part_1_sample() {
+        assert_eq!(part_1(sample()), 15);
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(input()), 502);
+    }
+
+    #[test]
+    fn test_basins() {
+        let basins = sample().basins();
+        assert_eq!(
+            basins.basins.iter().map(Basin::size).collect::<Vec<_>>(),
+            [3, 9, 14, 9]
+        );
+        assert_eq!(
+            basins
+                .basins
+                .iter()
+                .map(|basin| basin.low_point)
+                .collect::<Vec<_>>(),
+            [(1, 0), (9, 0), (2, 2), (6, 4)]
+        );
+        assert!(basins.basins[0].cells.contains(&(0, 1)));
+        assert_eq!(basins.labels[(0, 1)], Some(0));
+        assert_eq!(basins.labels[(2, 0)], None);
+        assert_eq!(basins.boundaries().len(), 11);
+        assert_eq!(basins.boundaries()[0], ((2, 0), vec![0, 2]));
+        assert!(basins.boundaries().contains(&((6, 2), vec![1, 2, 3])));
+        assert_eq!(
+            basins.to_string(),
+            "aA+#+bbbbB\n\
+             a+ccc+b+bb\n\
+             +cCccc+d+b\n\
+             ccccc+ddd+\n\
+             #c##+dDddd\n"
+        );
+
+        // These basins each have a low point, so they're the same as the
+        // ones part 2 finds from them.
+        let heights = input();
+        let sizes = heights
+            .basins()
+            .basins
+            .iter()
+            .map(Basin::size)
+            .sorted()
+            .collect::<Vec<_>>();
+        let expected = heights
+            .coords()
+            .filter(|&point| heights.is_low_point(point))
+            .map(|point| heights.basin_size(point))
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, expected);
+    }
+
+    #[test]
+    fn test_low_regions() {
+        let heights = sample();
+        let regions = heights.low_regions();
+        assert!(regions.iter().all(|region| !region.is_tie()));
+        assert_eq!(
+            regions
+                .iter()
+                .map(|region| region.cells[0])
+                .collect::<Vec<_>>(),
+            heights
+                .coords()
+                .filter(|&point| heights.is_low_point(point))
+                .collect::<Vec<_>>()
+        );
+
+        // The two 1s are tied, so neither is a low point, but they're one low
+        // region and the bottom of one basin.
+        let heights: HeightMap = "99999\n\
+                                  91199\n\
+                                  92399\n\
+                                  99999\n"
+            .parse()
+            .unwrap();
+        assert!(!heights.is_low_point((1, 1)));
+        assert_eq!(
+            heights.low_regions(),
+            [LowRegion {
+                height: 1,
+                cells: vec![(1, 1), (2, 1)]
+            }]
+        );
+        assert!(heights.low_regions()[0].is_tie());
+        assert_eq!(heights.basin_size((1, 1)), 4);
+        let basins = heights.basins();
+        assert_eq!(basins.basins[0].low_points, [(1, 1), (2, 1)]);
+        assert_eq!(part_2(heights), 4);
+
+        // Lowering the walls cuts off the 3.
+        let heights = "99999\n\
+                       91199\n\
+                       92399\n\
+                       99999\n"
+            .parse::<HeightMap>()
+            .unwrap()
+            .with_wall(3);
+        assert_eq!(heights.wall(), 3);
+        assert_eq!(heights.basin_size((1, 1)), 3);
+        assert_eq!(heights.basins().to_string(), "#####\n#Aa##\n#a###\n#####\n");
+        assert_eq!(heights.low_regions().len(), 1);
+    }
+
+    #[test]
+    fn test_rain() {
+        let heights: HeightMap = "9999999\n\
+                                  9307159\n\
+                                  9999999\n"
+            .parse()
+            .unwrap();
+        let rainfall = heights.rain(1, 20);
+        assert_eq!(rainfall.levels.len(), 20);
+        assert_eq!(rainfall.levels[0].row(1), [0, 0, 2, 0, 3, 0, 0]);
+        assert_eq!(rainfall.pooled(), 6 + 9 + 2 + 8 + 4);
+        assert_eq!(rainfall.drained, 20 * 21 - rainfall.pooled());
+        assert!(rainfall.levels[19]
+            .row(1)
+            .iter()
+            .zip(heights.heights.row(1))
+            .skip(1)
+            .take(5)
+            .all(|(&depth, &height)| depth + u32::from(height) == 9));
+
+        // Rain on the 7 would run left on the dry map, but runs right once
+        // there's water in the left basin, and both basins end up draining
+        // off the map over the 9s.
+        let first = &rainfall.overflows[0];
+        assert_eq!(
+            (first.step, first.from, first.to),
+            (1, Some((2, 1)), Some((4, 1)))
+        );
+        assert_eq!(first.path, [(3, 1), (4, 1)]);
+        assert!(rainfall
+            .overflows
+            .iter()
+            .any(|overflow| (overflow.from, overflow.to) == (Some((2, 1)), None)));
+        assert!(rainfall
+            .overflows
+            .iter()
+            .any(|overflow| (overflow.from, overflow.to) == (Some((4, 1)), None)));
+    }
+
+    #[test]
+    fn test_rain_fills_to_spill_height() {
+        // Each location fills up to the lowest height it'd have to climb to
+        // get off the map.
+        fn trapped(heights: &Grid<u8>) -> u64 {
+            let mut levels = Grid::new(heights.width(), heights.height(), None);
+            let mut queue = std::collections::BinaryHeap::new();
+            for point in heights.points() {
+                let (x, y) = point;
+                if x == 0 || y == 0 || x == heights.width() - 1 || y == heights.height() - 1 {
+                    levels[point] = Some(heights[point]);
+                    queue.push(std::cmp::Reverse((heights[point], point)));
+                }
+            }
+            while let Some(std::cmp::Reverse((level, point))) = queue.pop() {
+                for neighbor in heights.neighbors_4(point) {
+                    if levels[neighbor].is_none() {
+                        let level = level.max(heights[neighbor]);
+                        levels[neighbor] = Some(level);
+                        queue.push(std::cmp::Reverse((level, neighbor)));
+                    }
+                }
+            }
+            heights
+                .enumerate()
+                .map(|(point, &height)| u64::from(levels[point].unwrap() - height))
+                .sum()
+        }
+
+        let nested: HeightMap = "99999999\n\
+                                 95444449\n\
+                                 94101249\n\
+                                 94323249\n\
+                                 94444439\n\
+                                 99999999\n"
+            .parse()
+            .unwrap();
+        for heights in [sample(), nested] {
+            let rainfall = heights.rain(1, 10);
+            assert_eq!(rainfall.pooled(), trapped(&heights.heights));
+            assert_eq!(rainfall.levels[8], rainfall.levels[9]);
+        }
+    }
+
+    #[test]
+    fn test_part_2_sample() {
+        assert_eq!(part_2(sample()), 1134);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(input()), 1330560);
+    }
+}
@@ -0,0 +1,48 @@
+//! Advent of Code 2021.
+
+use crate::Day;
+
+pub mod day_10_syntax_scoring;
+pub mod day_11_dumbo_octopus;
+pub mod day_12_passage_pathing;
+pub mod day_13_transparent_origami;
+pub mod day_14_extended_polymerization;
+pub mod day_15_chiton;
+pub mod day_16_packet_decoder;
+pub mod day_17_trick_shot;
+pub mod day_18_snailfish;
+pub mod day_19_beacon_scanner;
+pub mod day_1_sonar_sweep;
+pub mod day_20_trench_map;
+pub mod day_2_dive;
+pub mod day_3_binary_diagnostic;
+pub mod day_4_giant_squid;
+pub mod day_5_hydrothermal_venture;
+pub mod day_6_lanternfish;
+pub mod day_7_the_treachery_of_whales;
+pub mod day_8_seven_segment_search;
+pub mod day_9_smoke_basin;
+
+/// Every day's solution, in order.
+pub static SOLUTIONS: &[Day] = &[
+    Day::new::<day_1_sonar_sweep::SonarSweep>(),
+    Day::new::<day_2_dive::Dive>(),
+    Day::new::<day_3_binary_diagnostic::BinaryDiagnostic>(),
+    Day::new::<day_4_giant_squid::GiantSquid>(),
+    Day::new::<day_5_hydrothermal_venture::HydrothermalVenture>(),
+    Day::new::<day_6_lanternfish::Lanternfish>(),
+    Day::new::<day_7_the_treachery_of_whales::TheTreacheryOfWhales>(),
+    Day::new::<day_8_seven_segment_search::SevenSegmentSearch>(),
+    Day::new::<day_9_smoke_basin::SmokeBasin>(),
+    Day::new::<day_10_syntax_scoring::SyntaxScoring>(),
+    Day::new::<day_11_dumbo_octopus::DumboOctopus>(),
+    Day::new::<day_12_passage_pathing::PassagePathing>(),
+    Day::new::<day_13_transparent_origami::TransparentOrigami>(),
+    Day::new::<day_14_extended_polymerization::ExtendedPolymerization>(),
+    Day::new::<day_15_chiton::Chiton>(),
+    Day::new::<day_16_packet_decoder::PacketDecoder>(),
+    Day::new::<day_17_trick_shot::TrickShot>(),
+    Day::new::<day_18_snailfish::SnailfishHomework>(),
+    Day::new::<day_19_beacon_scanner::BeaconScanner>(),
+    Day::new::<day_20_trench_map::TrenchMap>(),
+];